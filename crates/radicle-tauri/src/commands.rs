@@ -1,7 +1,10 @@
 pub mod auth;
 pub mod cob;
+pub mod diagnostics;
 pub mod diff;
 pub mod inbox;
+pub mod node;
+pub mod policy;
 pub mod profile;
 pub mod repo;
 pub mod startup;
@@ -15,7 +18,7 @@ use radicle_types::error::Error;
 /// work; running them inline on an `async` command would pin a runtime worker
 /// for the call's full duration and let a slow read (e.g. listing refs or
 /// jobs) stall every other command queued behind it. Cloning `AppState` is
-/// cheap — it only clones the `Profile` (paths and keys, not open handles).
+/// cheap — the clone shares the `Profile` behind an `Arc`.
 pub(crate) async fn blocking<T, F>(ctx: tauri::State<'_, AppState>, f: F) -> Result<T, Error>
 where
     F: FnOnce(AppState) -> Result<T, Error> + Send + 'static,
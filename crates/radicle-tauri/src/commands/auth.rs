@@ -1,11 +1,16 @@
 use std::str::FromStr;
+use std::time::Duration;
 
-use radicle::crypto::Seed;
 use radicle::crypto::ssh::{self, Passphrase};
+use radicle::crypto::{SecretKey, Seed};
 use radicle::node::Alias;
 use radicle::profile::env;
 use radicle_types::error::Error;
+use radicle_types::keys::KeyInfo;
+use radicle_types::traits::Profile;
+use radicle_types::traits::keys::Keys;
 use ssh_key::rand_core::{OsRng, RngCore};
+use zeroize::Zeroizing;
 
 use crate::AppState;
 
@@ -14,8 +19,8 @@ pub fn authenticate(
     ctx: tauri::State<AppState>,
     passphrase: Option<Passphrase>,
 ) -> Result<(), Error> {
-    let profile = &ctx.profile;
-    if !profile.keystore.is_encrypted()? {
+    let profile = &ctx.profile();
+    if !profile.keystore.is_encrypted()? || ctx.keys().is_unlocked(&profile.public_key) {
         return Ok(());
     }
     match ssh::agent::Agent::connect() {
@@ -34,11 +39,72 @@ pub fn authenticate(
                 )),
             }
         }
-        Err(e) if e.is_not_running() => Err(Error::AgentNotRunning)?,
+        // Without an agent, the key is kept in memory instead, until it goes
+        // unused for a while.
+        Err(e) if e.is_not_running() => match passphrase {
+            Some(passphrase) if !passphrase.is_empty() => {
+                let secret = secret_key(profile, passphrase)?;
+                ctx.keys().unlock(profile.public_key, secret);
+                Ok(())
+            }
+            _ => Err(Error::Crypto(
+                radicle::crypto::ssh::keystore::Error::PassphraseMissing,
+            )),
+        },
+        Err(e) => Err(e)?,
+    }
+}
+
+/// How long, in seconds, a key kept in memory may go unused before it's
+/// forgotten.
+#[tauri::command]
+pub fn unlock_timeout(ctx: tauri::State<AppState>) -> u64 {
+    ctx.keys().timeout().as_secs()
+}
+
+#[tauri::command]
+pub fn set_unlock_timeout(ctx: tauri::State<AppState>, secs: u64) {
+    ctx.keys().set_timeout(Duration::from_secs(secs));
+}
+
+/// Remove the key from the ssh-agent and from memory, so that signing asks
+/// for the passphrase again.
+#[tauri::command]
+pub fn lock(ctx: tauri::State<AppState>) -> Result<(), Error> {
+    let profile = ctx.profile();
+    ctx.keys().forget();
+    match ssh::agent::Agent::connect() {
+        Ok(mut agent) => {
+            if agent.request_identities()?.contains(&profile.public_key) {
+                agent.unregister(&profile.public_key)?;
+            }
+            Ok(())
+        }
+        // Without an agent there is nothing holding the key.
+        Err(e) if e.is_not_running() => Ok(()),
         Err(e) => Err(e)?,
     }
 }
 
+#[tauri::command]
+pub fn key_info(ctx: tauri::State<AppState>) -> Result<KeyInfo, Error> {
+    ctx.key_info()
+}
+
+#[tauri::command]
+pub fn export_public_key(ctx: tauri::State<AppState>) -> String {
+    ctx.export_public_key()
+}
+
+#[tauri::command]
+pub fn change_passphrase(
+    ctx: tauri::State<AppState>,
+    current: Option<Passphrase>,
+    new: Passphrase,
+) -> Result<(), Error> {
+    ctx.change_passphrase(current, new)
+}
+
 #[tauri::command]
 pub(crate) fn init(alias: String, passphrase: Passphrase) -> Result<(), Error> {
     let home = radicle::profile::home()?;
@@ -57,7 +123,8 @@ pub(crate) fn init(alias: String, passphrase: Passphrase) -> Result<(), Error> {
     let profile = radicle::Profile::init(home, alias, Some(passphrase.clone()), seed)?;
     match ssh::agent::Agent::connect() {
         Ok(mut agent) => register(&mut agent, &profile, passphrase.clone())?,
-        Err(e) if e.is_not_running() => return Err(Error::AgentNotRunning),
+        // The key is unlocked in memory when authenticating instead.
+        Err(e) if e.is_not_running() => {}
         Err(e) => Err(e)?,
     }
 
@@ -69,7 +136,18 @@ pub fn register(
     profile: &radicle::Profile,
     passphrase: ssh::Passphrase,
 ) -> Result<(), Error> {
-    let secret = profile
+    let secret = secret_key(profile, passphrase)?;
+    agent.register(&secret)?;
+
+    Ok(())
+}
+
+/// Decrypt the profile's secret key. A wrong passphrase is reported as such.
+fn secret_key(
+    profile: &radicle::Profile,
+    passphrase: ssh::Passphrase,
+) -> Result<Zeroizing<SecretKey>, Error> {
+    profile
         .keystore
         .secret_key(Some(passphrase))
         .map_err(|e| {
@@ -83,9 +161,5 @@ pub fn register(
         })?
         .ok_or(Error::Crypto(radicle::crypto::ssh::keystore::Error::Ssh(
             ssh_key::Error::Crypto,
-        )))?;
-
-    agent.register(&secret)?;
-
-    Ok(())
+        )))
 }
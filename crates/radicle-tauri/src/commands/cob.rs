@@ -1,19 +1,49 @@
 use std::path::PathBuf;
 
+use radicle::cob;
 use radicle::git;
 use radicle::identity;
 use radicle_types as types;
+use radicle_types::domain::search::models::search;
+use radicle_types::domain::search::traits::SearchService;
 use radicle_types::error::Error;
+use radicle_types::traits::Profile;
 use radicle_types::traits::thread::Thread;
 use tauri_plugin_clipboard_manager::ClipboardExt;
 use tauri_plugin_dialog::DialogExt;
 
 use crate::AppState;
+use crate::session::Session;
 
+pub mod identity;
 pub mod issue;
 pub mod job;
 pub mod patch;
 
+#[tauri::command]
+pub async fn search_cobs(
+    ctx: tauri::State<'_, AppState>,
+    session: tauri::State<'_, Session>,
+    query: String,
+    rids: Option<Vec<identity::RepoId>>,
+    kinds: Option<Vec<search::Kind>>,
+    author: Option<identity::Did>,
+    label: Option<cob::Label>,
+) -> Result<Vec<search::SearchHit>, Error> {
+    let aliases = ctx.profile().aliases();
+    let query = search::SearchQuery {
+        query,
+        rids,
+        kinds,
+        author,
+        label,
+    };
+
+    Ok(session
+        .search()
+        .search_hits(&query, search::MAX_HITS, &aliases)?)
+}
+
 #[tauri::command]
 pub async fn get_embed(
     ctx: tauri::State<'_, AppState>,
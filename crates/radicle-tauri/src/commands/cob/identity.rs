@@ -0,0 +1,46 @@
+use radicle::cob::identity::RevisionId;
+use radicle::identity;
+
+use radicle_types as types;
+use radicle_types::error::Error;
+use radicle_types::traits::identity::{Identities, IdentitiesMut};
+
+use crate::AppState;
+
+#[tauri::command]
+pub fn list_identity_revisions(
+    ctx: tauri::State<AppState>,
+    rid: identity::RepoId,
+) -> Result<Vec<types::cobs::identity::Revision>, Error> {
+    ctx.list_identity_revisions(rid)
+}
+
+#[tauri::command]
+pub fn identity_revision_diff(
+    ctx: tauri::State<AppState>,
+    rid: identity::RepoId,
+    base: Option<RevisionId>,
+    head: RevisionId,
+) -> Result<Option<types::cobs::identity::DocumentDiff>, Error> {
+    ctx.identity_revision_diff(rid, base, head)
+}
+
+#[tauri::command]
+pub fn propose_identity_revision(
+    ctx: tauri::State<AppState>,
+    rid: identity::RepoId,
+    proposal: types::cobs::identity::Proposal,
+    opts: types::cobs::CobOptions,
+) -> Result<types::cobs::identity::Revision, Error> {
+    ctx.propose_identity_revision(rid, proposal, opts)
+}
+
+#[tauri::command]
+pub fn edit_identity(
+    ctx: tauri::State<AppState>,
+    rid: identity::RepoId,
+    action: types::cobs::identity::Action,
+    opts: types::cobs::CobOptions,
+) -> Result<types::cobs::identity::Revision, Error> {
+    ctx.edit_identity(rid, action, opts)
+}
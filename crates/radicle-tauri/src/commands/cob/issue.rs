@@ -6,16 +6,15 @@ use radicle::storage::ReadStorage;
 
 use radicle::issue::TYPENAME;
 use radicle_types as types;
-use radicle_types::domain::issue::service::Service;
 use radicle_types::domain::issue::traits::IssueService;
 use radicle_types::error::Error;
-use radicle_types::outbound::sqlite::Sqlite;
 use radicle_types::traits::Profile;
 use radicle_types::traits::cobs::Cobs;
 use radicle_types::traits::issue::Issues;
 use radicle_types::traits::issue::IssuesMut;
 
 use crate::AppState;
+use crate::session::Session;
 
 #[tauri::command]
 pub fn create_issue(
@@ -41,17 +40,41 @@ pub fn edit_issue(
 #[tauri::command]
 pub(crate) async fn list_issues(
     ctx: tauri::State<'_, AppState>,
-    issue_service: tauri::State<'_, Service<Sqlite>>,
+    session: tauri::State<'_, Session>,
     rid: identity::RepoId,
     status: Option<types::cobs::query::IssueStatus>,
+    filter: Option<types::cobs::query::ListFilter>,
     skip: Option<usize>,
+    after: Option<types::cobs::Cursor>,
     // None: return all issues, `skip` is ignored.
     take: Option<usize>,
 ) -> Result<types::cobs::PaginatedQuery<Vec<types::cobs::issue::Issue>>, Error> {
     let profile = ctx.profile();
     let aliases = profile.aliases();
 
-    Ok(issue_service.list_paginated(rid, status.unwrap_or_default(), skip, take, &aliases)?)
+    Ok(session.issues().list_paginated(
+        rid,
+        status.unwrap_or_default(),
+        &filter.unwrap_or_default(),
+        skip,
+        after,
+        take,
+        &aliases,
+    )?)
+}
+
+#[tauri::command]
+pub(crate) async fn issue_counts(
+    ctx: tauri::State<'_, AppState>,
+    session: tauri::State<'_, Session>,
+    rid: identity::RepoId,
+    status: Option<types::cobs::query::IssueStatus>,
+) -> Result<types::cobs::FacetedCounts<types::domain::issue::models::issue::IssueCounts>, Error> {
+    let aliases = ctx.profile().aliases();
+
+    Ok(session
+        .issues()
+        .faceted_counts(rid, status.unwrap_or_default(), &aliases)?)
 }
 
 #[tauri::command]
@@ -87,9 +110,10 @@ pub async fn rebuild_issue_cache(
     rid: identity::RepoId,
     on_event: tauri::ipc::Channel<types::cobs::CacheEvent>,
 ) -> Result<(), Error> {
-    let repo = ctx.profile.storage.repository(rid)?;
-    let signer = ctx.profile.signer()?;
-    let mut issues = ctx.profile.issues_mut(&repo, &signer)?;
+    let profile = ctx.profile();
+    let repo = profile.storage.repository(rid)?;
+    let signer = ctx.signer()?;
+    let mut issues = profile.issues_mut(&repo, &signer)?;
     on_event.send(types::cobs::CacheEvent::Started { rid })?;
     issues.write_all(|result, progress| {
         match result {
@@ -7,24 +7,25 @@ use radicle::{git, identity};
 use radicle_types as types;
 use radicle_types::cobs;
 use radicle_types::domain::patch::models;
-use radicle_types::domain::patch::service::Service;
 use radicle_types::domain::patch::traits::PatchService;
 use radicle_types::error::Error;
-use radicle_types::outbound::sqlite::Sqlite;
 use radicle_types::traits::Profile;
 use radicle_types::traits::cobs::Cobs;
 use radicle_types::traits::patch::Patches;
 use radicle_types::traits::patch::PatchesMut;
 
 use crate::AppState;
+use crate::session::Session;
 
 #[tauri::command]
 pub async fn list_patches(
     ctx: tauri::State<'_, AppState>,
-    sqlite_service: tauri::State<'_, Service<Sqlite>>,
+    session: tauri::State<'_, Session>,
     rid: identity::RepoId,
     status: Option<types::cobs::query::PatchStatus>,
+    filter: Option<types::cobs::query::ListFilter>,
     skip: Option<usize>,
+    after: Option<types::cobs::Cursor>,
     // None: return all patches, `skip` is ignored.
     take: Option<usize>,
 ) -> Result<types::cobs::PaginatedQuery<Vec<models::patch::Patch>>, Error> {
@@ -32,7 +33,38 @@ pub async fn list_patches(
     let aliases = profile.aliases();
     let delegates = Vec::from(profile.storage.repository(rid)?.delegates()?);
 
-    Ok(sqlite_service.list_paginated(rid, status, skip, take, &delegates, &aliases)?)
+    Ok(session.patches().list_paginated(
+        rid,
+        status,
+        &filter.unwrap_or_default(),
+        skip,
+        after,
+        take,
+        &delegates,
+        &aliases,
+    )?)
+}
+
+#[tauri::command]
+pub async fn patch_counts(
+    ctx: tauri::State<'_, AppState>,
+    session: tauri::State<'_, Session>,
+    rid: identity::RepoId,
+    status: Option<types::cobs::query::PatchStatus>,
+) -> Result<types::cobs::FacetedCounts<models::patch::PatchCounts>, Error> {
+    let aliases = ctx.profile().aliases();
+
+    Ok(session.patches().faceted_counts(rid, status, &aliases)?)
+}
+
+#[tauri::command]
+pub fn create_patch(
+    ctx: tauri::State<AppState>,
+    rid: identity::RepoId,
+    new: models::patch::NewPatch,
+    opts: cobs::CobOptions,
+) -> Result<models::patch::Patch, Error> {
+    ctx.create_patch(rid, new, opts)
 }
 
 #[tauri::command]
@@ -64,6 +96,15 @@ pub fn edit_patch(
     ctx.edit_patch(rid, cob_id, action, opts)
 }
 
+#[tauri::command]
+pub fn create_patch_revision(
+    ctx: tauri::State<AppState>,
+    args: models::patch::CreateRevisionArgs,
+    opts: cobs::CobOptions,
+) -> Result<models::patch::Patch, Error> {
+    ctx.create_patch_revision(args, opts)
+}
+
 #[tauri::command]
 pub fn delete_patch(
     ctx: tauri::State<AppState>,
@@ -97,9 +138,10 @@ pub async fn rebuild_patch_cache(
     rid: identity::RepoId,
     on_event: tauri::ipc::Channel<cobs::CacheEvent>,
 ) -> Result<(), Error> {
-    let repo = ctx.profile.storage.repository(rid)?;
-    let signer = ctx.profile.signer()?;
-    let mut patches = ctx.profile.patches_mut(&repo, &signer)?;
+    let profile = ctx.profile();
+    let repo = profile.storage.repository(rid)?;
+    let signer = ctx.signer()?;
+    let mut patches = profile.patches_mut(&repo, &signer)?;
     on_event.send(types::cobs::CacheEvent::Started { rid })?;
     patches.write_all(|result, progress| {
         match result {
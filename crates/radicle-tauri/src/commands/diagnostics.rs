@@ -0,0 +1,33 @@
+use radicle::identity::RepoId;
+use radicle_types as types;
+use radicle_types::error::Error;
+use radicle_types::traits::diagnostics::Diagnostics;
+
+use crate::AppState;
+use crate::commands::blocking;
+use crate::session::Session;
+
+#[tauri::command]
+pub async fn check_storage(
+    ctx: tauri::State<'_, AppState>,
+) -> Result<types::diagnostics::Report, Error> {
+    blocking(ctx, |ctx| ctx.check_storage()).await
+}
+
+#[tauri::command]
+pub async fn repair_cache(
+    ctx: tauri::State<'_, AppState>,
+) -> Result<types::diagnostics::Report, Error> {
+    blocking(ctx, |ctx| ctx.repair_cache()).await
+}
+
+#[tauri::command]
+pub async fn repair_repo(
+    ctx: tauri::State<'_, AppState>,
+    session: tauri::State<'_, Session>,
+    rid: RepoId,
+    repair: types::diagnostics::Repair,
+) -> Result<types::diagnostics::RepoHealth, Error> {
+    let fetches = session.fetches();
+    blocking(ctx, move |ctx| ctx.repair_repo(rid, repair, &fetches)).await
+}
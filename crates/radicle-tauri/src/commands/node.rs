@@ -0,0 +1,45 @@
+use tauri::ipc::Channel;
+
+use radicle_types as types;
+use radicle_types::error::Error;
+use radicle_types::traits::Profile;
+use radicle_types::traits::node::NodeInfo;
+
+use crate::AppState;
+use crate::commands::blocking;
+use crate::process::NodeProcess;
+
+#[tauri::command]
+pub async fn node_status(ctx: tauri::State<'_, AppState>) -> Result<types::node::Status, Error> {
+    blocking(ctx, |ctx| ctx.node_status()).await
+}
+
+#[tauri::command]
+pub async fn start_node(
+    ctx: tauri::State<'_, AppState>,
+    node: tauri::State<'_, NodeProcess>,
+) -> Result<(), Error> {
+    node.start(&ctx.profile())
+}
+
+#[tauri::command]
+pub async fn stop_node(
+    ctx: tauri::State<'_, AppState>,
+    node: tauri::State<'_, NodeProcess>,
+) -> Result<(), Error> {
+    // Waiting for the node to exit can take a while.
+    let node = (*node).clone();
+    let profile = ctx.profile();
+    tauri::async_runtime::spawn_blocking(move || node.stop(&profile)).await?
+}
+
+/// Stream the output of the current profile's node to `on_line`, starting
+/// with what it logged recently.
+#[tauri::command]
+pub fn node_logs(
+    ctx: tauri::State<'_, AppState>,
+    node: tauri::State<'_, NodeProcess>,
+    on_line: Channel<String>,
+) {
+    node.subscribe(&ctx.profile(), on_line);
+}
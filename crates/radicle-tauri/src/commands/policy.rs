@@ -0,0 +1,39 @@
+use radicle::identity::RepoId;
+use radicle::node::{Alias, NodeId};
+use radicle_types as types;
+use radicle_types::error::Error;
+use radicle_types::traits::policy::Policies;
+
+use crate::AppState;
+use crate::commands::blocking;
+
+#[tauri::command]
+pub async fn list_follow_policies(
+    ctx: tauri::State<'_, AppState>,
+) -> Result<Vec<types::policy::FollowPolicy>, Error> {
+    blocking(ctx, |ctx| ctx.list_follow_policies()).await
+}
+
+#[tauri::command]
+pub fn follow(
+    ctx: tauri::State<'_, AppState>,
+    nid: NodeId,
+    alias: Option<Alias>,
+) -> Result<bool, Error> {
+    ctx.follow(nid, alias)
+}
+
+#[tauri::command]
+pub fn unfollow(ctx: tauri::State<'_, AppState>, nid: NodeId) -> Result<bool, Error> {
+    ctx.unfollow(nid)
+}
+
+#[tauri::command]
+pub fn block_node(ctx: tauri::State<'_, AppState>, nid: NodeId) -> Result<bool, Error> {
+    ctx.block_node(nid)
+}
+
+#[tauri::command]
+pub fn block_repo(ctx: tauri::State<'_, AppState>, rid: RepoId) -> Result<bool, Error> {
+    ctx.block_repo(rid)
+}
@@ -1,15 +1,65 @@
+use std::path::PathBuf;
+
+use tauri::AppHandle;
+
 use radicle::node::NodeId;
-use radicle_types::config::Config;
-use radicle_types::traits::Profile;
+use radicle_types::config::{Config, ConfigEdit, ConfigUpdate};
+use radicle_types::error::Error;
+use radicle_types::profiles::{self, ProfileEntry};
+use radicle_types::traits::{Profile, ProfileMut};
 
 use crate::AppState;
+use crate::commands::startup::profiles_file;
+use crate::session;
 
 #[tauri::command]
 pub fn config(ctx: tauri::State<AppState>) -> Config {
     ctx.config()
 }
 
+#[tauri::command]
+pub fn update_config(ctx: tauri::State<AppState>, edit: ConfigEdit) -> Result<ConfigUpdate, Error> {
+    ctx.update_config(edit)
+}
+
 #[tauri::command]
 pub fn alias(ctx: tauri::State<AppState>, nid: NodeId) -> Option<radicle::node::Alias> {
     ctx.alias(nid)
 }
+
+#[tauri::command]
+pub fn list_profiles(app: AppHandle) -> Result<Vec<ProfileEntry>, Error> {
+    Ok(profiles::Registry::open(profiles_file(&app)?)?.entries())
+}
+
+/// Register the profile at `home`, which has to load.
+#[tauri::command]
+pub fn add_profile(app: AppHandle, home: PathBuf) -> Result<(), Error> {
+    let profile = profiles::load(&home)?;
+    profiles::Registry::open(profiles_file(&app)?)?.add(profile.home().path().to_path_buf())
+}
+
+/// Unregister the profile at `home`. Homes are registered the way they
+/// resolve when loaded, so `home` is resolved the same way first; a home that
+/// doesn't load anymore is matched as given.
+#[tauri::command]
+pub fn remove_profile(app: AppHandle, home: PathBuf) -> Result<(), Error> {
+    let home = match profiles::load(&home) {
+        Ok(profile) => profile.home().path().to_path_buf(),
+        Err(_) => home,
+    };
+    profiles::Registry::open(profiles_file(&app)?)?.remove(&home)
+}
+
+/// Make the profile at `home` the one the app works with, without
+/// restarting it.
+#[tauri::command]
+pub fn switch_profile(app: AppHandle, home: PathBuf) -> Result<Config, Error> {
+    let mut registry = profiles::Registry::open(profiles_file(&app)?)?;
+    let profile = profiles::load(&home)?;
+    let home = profile.home().path().to_path_buf();
+    let config = session::open(&app, profile)?;
+    registry.activate(home)?;
+
+    Ok(config)
+}
@@ -1,3 +1,5 @@
+use tauri::ipc::Channel;
+
 use radicle::git;
 use radicle::identity::RepoId;
 use radicle::node::NodeId;
@@ -7,6 +9,7 @@ use radicle_types::traits::repo::{Repo, Show};
 
 use crate::AppState;
 use crate::commands::blocking;
+use crate::session::Session;
 
 #[tauri::command]
 pub async fn list_repos(
@@ -36,6 +39,14 @@ pub async fn list_repo_refs(
     blocking(ctx, move |ctx| ctx.list_repo_refs(rid)).await
 }
 
+#[tauri::command]
+pub async fn canonical_rules(
+    ctx: tauri::State<'_, AppState>,
+    rid: RepoId,
+) -> Result<Vec<types::repo::CanonicalRule>, Error> {
+    blocking(ctx, move |ctx| ctx.canonical_rules(rid)).await
+}
+
 #[tauri::command]
 pub async fn repo_by_id(
     ctx: tauri::State<'_, AppState>,
@@ -117,10 +128,11 @@ pub async fn list_repo_commits(
     peer: Option<NodeId>,
     revision: Option<String>,
     skip: Option<usize>,
+    after: Option<types::cobs::Cursor>,
     take: Option<usize>,
 ) -> Result<types::cobs::PaginatedQuery<Vec<types::repo::Commit>>, Error> {
     blocking(ctx, move |ctx| {
-        ctx.list_repo_commits(rid, head, peer, revision, skip, take)
+        ctx.list_repo_commits(rid, head, peer, revision, skip, after, take)
     })
     .await
 }
@@ -146,8 +158,51 @@ pub async fn repo_commit(
 }
 
 #[tauri::command]
-pub fn seed(ctx: tauri::State<'_, AppState>, rid: RepoId) -> Result<(), Error> {
-    ctx.seed(rid)
+pub async fn init_repo(
+    ctx: tauri::State<'_, AppState>,
+    args: types::repo::InitRepo,
+) -> Result<RepoId, Error> {
+    blocking(ctx, move |ctx| ctx.init_repo(args)).await
+}
+
+#[tauri::command]
+pub async fn disk_usage(ctx: tauri::State<'_, AppState>) -> Result<types::repo::DiskUsage, Error> {
+    blocking(ctx, |ctx| ctx.disk_usage()).await
+}
+
+#[tauri::command]
+pub async fn repo_disk_usage(
+    ctx: tauri::State<'_, AppState>,
+    rid: RepoId,
+) -> Result<types::repo::RepoUsage, Error> {
+    blocking(ctx, move |ctx| ctx.repo_disk_usage(rid)).await
+}
+
+#[tauri::command]
+pub async fn remove_repo(
+    ctx: tauri::State<'_, AppState>,
+    session: tauri::State<'_, Session>,
+    rid: RepoId,
+) -> Result<(), Error> {
+    let inbox = session.inbox();
+    blocking(ctx, move |ctx| ctx.remove_repo(&inbox, rid)).await
+}
+
+#[tauri::command]
+pub async fn gc_repo(
+    ctx: tauri::State<'_, AppState>,
+    rid: RepoId,
+) -> Result<types::repo::RepoUsage, Error> {
+    blocking(ctx, move |ctx| ctx.gc_repo(rid)).await
+}
+
+#[tauri::command]
+pub fn seed(
+    ctx: tauri::State<'_, AppState>,
+    rid: RepoId,
+    scope: Option<types::repo::Scope>,
+) -> Result<(), Error> {
+    ctx.seed(rid, scope)
 }
 
 #[tauri::command]
@@ -155,7 +210,65 @@ pub fn unseed(ctx: tauri::State<'_, AppState>, rid: RepoId) -> Result<(), Error>
     ctx.unseed(rid)
 }
 
+#[tauri::command]
+pub async fn list_seed_policies(
+    ctx: tauri::State<'_, AppState>,
+) -> Result<Vec<types::repo::SeedPolicy>, Error> {
+    blocking(ctx, |ctx| ctx.list_seed_policies()).await
+}
+
+#[tauri::command]
+pub async fn sync_status(
+    ctx: tauri::State<'_, AppState>,
+    rid: RepoId,
+) -> Result<Vec<types::cobs::repo::SeedSync>, Error> {
+    blocking(ctx, move |ctx| ctx.sync_status(rid)).await
+}
+
+#[tauri::command]
+pub async fn sync_repo(
+    ctx: tauri::State<'_, AppState>,
+    rid: RepoId,
+    on_event: Channel<types::cobs::repo::SyncEvent>,
+) -> Result<(), Error> {
+    blocking(ctx, move |ctx| {
+        ctx.sync_repo(rid, |event| {
+            if let Err(e) = on_event.send(event) {
+                log::warn!("Not able to send sync progress: {e}");
+            }
+        })
+    })
+    .await
+}
+
 #[tauri::command]
 pub async fn seeded_not_replicated(ctx: tauri::State<'_, AppState>) -> Result<Vec<RepoId>, Error> {
     ctx.seeded_not_replicated()
 }
+
+#[tauri::command]
+pub async fn pending_repos(
+    ctx: tauri::State<'_, AppState>,
+    session: tauri::State<'_, Session>,
+) -> Result<Vec<types::repo::PendingRepo>, Error> {
+    let fetches = session.fetches();
+    blocking(ctx, move |ctx| ctx.pending_repos(&fetches)).await
+}
+
+#[tauri::command]
+pub async fn fetch_repo(
+    ctx: tauri::State<'_, AppState>,
+    session: tauri::State<'_, Session>,
+    rid: RepoId,
+    on_event: Channel<types::cobs::repo::SyncEvent>,
+) -> Result<types::repo::FetchAttempt, Error> {
+    let fetches = session.fetches();
+    blocking(ctx, move |ctx| {
+        ctx.fetch_repo(rid, &fetches, |event| {
+            if let Err(e) = on_event.send(event) {
+                log::warn!("Not able to send fetch progress: {e}");
+            }
+        })
+    })
+    .await
+}
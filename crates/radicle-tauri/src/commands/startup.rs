@@ -1,12 +1,18 @@
-use tauri::{AppHandle, Emitter, Manager};
+use std::path::PathBuf;
 
-use radicle::cob::cache::COBS_DB_FILE;
-use radicle::node::{Handle, NOTIFICATIONS_DB_FILE, Node};
+use tauri::{AppHandle, Manager};
+
+use radicle::profile::env;
 
 use radicle_types::config::{Config, Version};
 use radicle_types::error::Error;
 use radicle_types::traits::Profile;
-use radicle_types::{AppState, domain};
+use radicle_types::{AppState, profiles};
+
+use crate::session;
+
+/// The Radicle homes registered with the app, kept in the app's own data dir.
+const PROFILES_FILE: &str = "profiles.json";
 
 #[tauri::command]
 pub(crate) fn version(app: AppHandle) -> Result<Version, Error> {
@@ -16,28 +22,27 @@ pub(crate) fn version(app: AppHandle) -> Result<Version, Error> {
     })
 }
 
-/// Check whether a binary can be found in the most common paths on Unix-like systems.
+/// Find a binary in the most common paths on Unix-like systems.
 /// We don't bother checking the `$PATH` variable, as we're only looking for very standard tools
 /// and prefer not to make this too complex.
 #[cfg(unix)]
-fn exists(cmd: &str) -> bool {
+fn find(cmd: &str) -> Option<PathBuf> {
     // Some common paths where system-installed binaries are found.
     const PATHS: &[&str] = &["/usr/local/bin", "/usr/bin", "/bin"];
 
-    for dir in PATHS {
-        if std::path::Path::new(dir).join(cmd).exists() {
-            return true;
-        }
-    }
-    false
+    PATHS
+        .iter()
+        .map(|dir| std::path::Path::new(dir).join(cmd))
+        .find(|path| path.exists())
 }
 
-/// Check whether a binary can be found on `$PATH`.
+/// Check whether a binary can be found on `$PATH`, in which case it can be
+/// run by name.
 /// See:
 ///  - <https://devblogs.microsoft.com/scripting/weekend-scripter-where-exethe-what-why-and-how/>
 ///  - <https://learn.microsoft.com/windows-server/administration/windows-commands/where>
 #[cfg(windows)]
-fn exists(cmd: &str) -> bool {
+fn find(cmd: &str) -> Option<PathBuf> {
     use std::os::windows::process::CommandExt;
 
     // See <https://learn.microsoft.com/windows/win32/procthread/process-creation-flags#flags>.
@@ -48,59 +53,47 @@ fn exists(cmd: &str) -> bool {
         .arg("$PATH:".to_owned() + cmd)
         .creation_flags(CREATE_NO_WINDOW)
         .output()
-        .map(|output| output.status.success())
-        .unwrap_or_default()
+        .is_ok_and(|output| output.status.success())
+        .then(|| PathBuf::from(cmd))
 }
 
-#[tauri::command]
-pub(crate) fn check_radicle_cli(ctx: tauri::State<AppState>) -> Result<(), Error> {
-    let rad = ctx.profile().home().path().join("bin").join("rad");
-    if rad.exists() {
-        return Ok(());
+/// Locate a Radicle binary, preferring the one installed into the profile's
+/// home by the Radicle installer.
+pub(crate) fn locate(profile: &radicle::Profile, cmd: &str) -> Option<PathBuf> {
+    let installed = profile.home().path().join("bin").join(cmd);
+    if installed.exists() {
+        return Some(installed);
     }
 
-    if exists("rad") {
-        return Ok(());
-    }
+    find(cmd)
+}
 
-    Err(Error::RadicleNotInstalled)
+#[tauri::command]
+pub(crate) fn check_radicle_cli(ctx: tauri::State<AppState>) -> Result<(), Error> {
+    locate(&ctx.profile(), "rad")
+        .map(|_| ())
+        .ok_or(Error::RadicleNotInstalled)
 }
 
 #[tauri::command]
 pub(crate) fn startup(app: AppHandle) -> Result<Config, Error> {
-    let profile = radicle::Profile::load()?;
-    let home = profile.home();
-
-    let cobs_cache = radicle::cob::cache::Store::open(home.cobs().join(COBS_DB_FILE))?;
-    cobs_cache.check_version()?;
-
-    let inbox_db = radicle_types::outbound::sqlite::Sqlite::reader(
-        profile.node().join(NOTIFICATIONS_DB_FILE),
-    )?;
-    let cobs_db =
-        radicle_types::outbound::sqlite::Sqlite::reader(profile.cobs().join(COBS_DB_FILE))?;
+    let mut registry = profiles::Registry::open(profiles_file(&app)?)?;
 
-    let inbox_service = domain::inbox::service::Service::new(inbox_db);
-    let patch_service = domain::patch::service::Service::new(cobs_db.clone());
-    let issue_service = domain::issue::service::Service::new(cobs_db);
+    // A home set in the environment wins, so that the app and the CLI agree
+    // when started from the same shell.
+    let profile = match registry.active() {
+        Some(home) if std::env::var_os(env::RAD_HOME).is_none() => profiles::load(home)?,
+        _ => radicle::Profile::load()?,
+    };
+    registry.activate(profile.home().path().to_path_buf())?;
 
-    let node_handle = app.app_handle().clone();
-
-    let node = Node::new(profile.home().socket_from_env());
-
-    app.manage(inbox_service);
-    app.manage(patch_service);
-    app.manage(issue_service);
-
-    tauri::async_runtime::spawn(async move {
-        loop {
-            let _ = node_handle.emit("node_running", node.is_running());
-            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-        }
-    });
+    session::open(&app, profile)
+}
 
-    let state = AppState { profile };
-    app.manage(state.clone());
+/// Where the registered profiles are kept, in the app's own data dir.
+pub(crate) fn profiles_file(app: &AppHandle) -> Result<PathBuf, Error> {
+    let data_dir = app.path().app_data_dir()?;
+    std::fs::create_dir_all(&data_dir)?;
 
-    Ok(state.config())
+    Ok(data_dir.join(PROFILES_FILE))
 }
@@ -1,8 +1,11 @@
 mod commands;
+mod process;
+mod session;
+mod watcher;
 
 use radicle_types::AppState;
 
-use commands::{auth, cob, diff, inbox, profile, repo, startup, thread};
+use commands::{auth, cob, diagnostics, diff, inbox, node, policy, profile, repo, startup, thread};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -30,24 +33,39 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_window_state::Builder::default().build())
+        .manage(process::NodeProcess::default())
         .invoke_handler(tauri::generate_handler![
             auth::authenticate,
+            auth::change_passphrase,
+            auth::export_public_key,
             auth::init,
+            auth::key_info,
+            auth::lock,
+            auth::set_unlock_timeout,
+            auth::unlock_timeout,
             cob::get_embed,
+            cob::identity::edit_identity,
+            cob::identity::identity_revision_diff,
+            cob::identity::list_identity_revisions,
+            cob::identity::propose_identity_revision,
             cob::issue::activity_by_issue,
             cob::issue::comment_threads_by_issue_id,
             cob::issue::create_issue,
             cob::issue::edit_issue,
             cob::issue::issue_by_id,
+            cob::issue::issue_counts,
             cob::issue::list_issues,
             cob::issue::rebuild_issue_cache,
             cob::job::list_jobs,
             cob::patch::activity_by_patch,
+            cob::patch::create_patch,
             cob::patch::delete_patch,
             cob::patch::edit_patch,
             cob::patch::list_patches,
             cob::patch::patch_by_id,
+            cob::patch::patch_counts,
             cob::patch::create_patch_review,
+            cob::patch::create_patch_revision,
             cob::patch::rebuild_patch_cache,
             cob::patch::revisions_by_patch,
             cob::patch::revisions_by_patch,
@@ -55,6 +73,10 @@ pub fn run() {
             cob::save_embed_by_clipboard,
             cob::save_embed_by_path,
             cob::save_embed_to_disk,
+            cob::search_cobs,
+            diagnostics::check_storage,
+            diagnostics::repair_cache,
+            diagnostics::repair_repo,
             diff::get_diff,
             diff::get_diff_text,
             diff::save_diff_to_disk,
@@ -62,23 +84,49 @@ pub fn run() {
             inbox::clear_notifications,
             inbox::notification_count,
             inbox::list_notifications,
+            inbox::mark_notifications,
+            node::node_logs,
+            node::node_status,
+            node::start_node,
+            node::stop_node,
+            policy::block_node,
+            policy::block_repo,
+            policy::follow,
+            policy::list_follow_policies,
+            policy::unfollow,
+            profile::add_profile,
             profile::alias,
             profile::config,
+            profile::list_profiles,
+            profile::remove_profile,
+            profile::switch_profile,
+            profile::update_config,
+            repo::canonical_rules,
             repo::diff_stats,
+            repo::disk_usage,
+            repo::gc_repo,
             repo::list_commits,
             repo::list_repo_commits,
+            repo::list_seed_policies,
             repo::list_repo_refs,
             repo::list_repos,
             repo::list_repos_summary,
+            repo::remove_repo,
             repo::repo_by_id,
             repo::repo_commit_count,
             repo::repo_commit,
             repo::repo_count,
+            repo::repo_disk_usage,
             repo::repo_readme,
             repo::repo_tree,
             repo::repo_blob,
+            repo::init_repo,
             repo::seed,
             repo::seeded_not_replicated,
+            repo::pending_repos,
+            repo::fetch_repo,
+            repo::sync_repo,
+            repo::sync_status,
             repo::unseed,
             startup::startup,
             startup::version,
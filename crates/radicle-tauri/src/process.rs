@@ -0,0 +1,164 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time;
+
+use radicle::crypto::ssh;
+use radicle::node::{Handle, Node};
+use radicle::profile::env;
+use tauri::ipc::Channel;
+
+use radicle_types::error::Error;
+
+use crate::commands::startup::locate;
+
+/// How many lines of node output are kept for subscribers that join late.
+const LOG_CAPACITY: usize = 1000;
+
+/// How long the node gets to shut down before the process is killed.
+const SHUTDOWN_TIMEOUT: time::Duration = time::Duration::from_secs(10);
+
+#[derive(Default)]
+struct Logs {
+    /// The most recent output, oldest first.
+    lines: VecDeque<String>,
+    subscribers: Vec<Channel<String>>,
+}
+
+impl Logs {
+    fn push(&mut self, line: String) {
+        // A failed send means the frontend dropped the channel.
+        self.subscribers
+            .retain(|channel| channel.send(line.clone()).is_ok());
+        if self.lines.len() == LOG_CAPACITY {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+}
+
+/// The `radicle-node` processes started from the app and what they have
+/// logged so far, both by the home of the profile they run for.
+#[derive(Default, Clone)]
+pub(crate) struct NodeProcess {
+    children: Arc<Mutex<HashMap<PathBuf, Child>>>,
+    logs: Arc<Mutex<HashMap<PathBuf, Logs>>>,
+}
+
+impl NodeProcess {
+    /// Spawn `radicle-node` for the profile, unless a node is already
+    /// listening on its control socket, or one we spawned for it is still
+    /// starting up. Fails if the node wouldn't be able to sign.
+    pub(crate) fn start(&self, profile: &radicle::Profile) -> Result<(), Error> {
+        if Node::new(profile.home().socket_from_env()).is_running() {
+            return Ok(());
+        }
+        if !can_sign(profile)? {
+            return Err(Error::KeyNotInAgent);
+        }
+        let home = profile.home().path().to_path_buf();
+        let mut children = lock(&self.children);
+        if let Some(child) = children.get_mut(&home) {
+            if child.try_wait()?.is_none() {
+                return Ok(());
+            }
+            // The previous node exited on its own and is now reaped.
+            children.remove(&home);
+        }
+        let bin = locate(profile, "radicle-node").ok_or(Error::RadicleNotInstalled)?;
+        let mut child = Command::new(bin)
+            .env(env::RAD_HOME, profile.home().path())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        if let Some(stdout) = child.stdout.take() {
+            self.forward(home.clone(), stdout);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            self.forward(home.clone(), stderr);
+        }
+        children.insert(home, child);
+
+        Ok(())
+    }
+
+    /// Ask the node to shut down over its control socket, which also works
+    /// for a node started outside the app. A process we spawned that doesn't
+    /// exit in time is killed.
+    pub(crate) fn stop(&self, profile: &radicle::Profile) -> Result<(), Error> {
+        let node = Node::new(profile.home().socket_from_env());
+        if node.is_running() {
+            node.shutdown()?;
+        }
+
+        let Some(mut child) = lock(&self.children).remove(profile.home().path()) else {
+            return Ok(());
+        };
+        let started = time::Instant::now();
+        while child.try_wait()?.is_none() {
+            if started.elapsed() > SHUTDOWN_TIMEOUT {
+                log::warn!("Node didn't shut down in time, killing it");
+                child.kill()?;
+                child.wait()?;
+                break;
+            }
+            std::thread::sleep(time::Duration::from_millis(100));
+        }
+
+        Ok(())
+    }
+
+    /// Send the buffered output of the profile's node to `channel`, followed
+    /// by every new line.
+    pub(crate) fn subscribe(&self, profile: &radicle::Profile, channel: Channel<String>) {
+        let mut logs = lock(&self.logs);
+        let logs = logs.entry(profile.home().path().to_path_buf()).or_default();
+        for line in &logs.lines {
+            if channel.send(line.clone()).is_err() {
+                return;
+            }
+        }
+        logs.subscribers.push(channel);
+    }
+
+    fn forward(&self, home: PathBuf, output: impl Read + Send + 'static) {
+        let logs = self.logs.clone();
+
+        std::thread::spawn(move || {
+            for line in BufReader::new(output).lines() {
+                match line {
+                    Ok(line) => lock(&logs).entry(home.clone()).or_default().push(line),
+                    Err(e) => {
+                        log::warn!("Not able to read node output: {e}");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Whether a node started for `profile` gets at its secret key: the key isn't
+/// encrypted, its passphrase is in the environment the node inherits, or the
+/// ssh-agent holds it. A key unlocked in the app isn't handed to the node.
+fn can_sign(profile: &radicle::Profile) -> Result<bool, Error> {
+    if !profile.keystore.is_encrypted()? || std::env::var_os(env::RAD_PASSPHRASE).is_some() {
+        return Ok(true);
+    }
+
+    match ssh::agent::Agent::connect() {
+        Ok(mut agent) => Ok(agent.request_identities()?.contains(&profile.public_key)),
+        Err(e) if e.is_not_running() => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// The logs and child handles stay usable even if a thread panicked while
+/// holding them.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
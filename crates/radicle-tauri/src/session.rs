@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use radicle::cob::cache::COBS_DB_FILE;
+use radicle::node::{Handle, NOTIFICATIONS_DB_FILE, Node};
+
+use radicle_types::AppState;
+use radicle_types::config::Config;
+use radicle_types::domain::{inbox, issue, patch, search};
+use radicle_types::error::Error;
+use radicle_types::outbound::inbox::InboxStore;
+use radicle_types::outbound::search::SearchIndex;
+use radicle_types::outbound::sqlite::Sqlite;
+use radicle_types::repo::FetchLog;
+use radicle_types::traits::{Profile, ProfileMut};
+
+/// Full-text index over the COB cache, kept in the app's own data dir.
+const SEARCH_DB_FILE: &str = "search.db";
+
+/// Read, snoozed and done states of notifications, kept in the app's own data dir.
+const INBOX_DB_FILE: &str = "inbox.db";
+
+/// The services opened on the active profile's databases. Switching profiles
+/// replaces them, so commands get them from here rather than managing each
+/// one on its own.
+pub(crate) struct Session {
+    services: RwLock<Services>,
+    /// Set when the session is replaced, to stop the threads watching the
+    /// previous profile.
+    stopped: RwLock<Arc<AtomicBool>>,
+}
+
+#[derive(Clone)]
+struct Services {
+    patches: patch::service::Service<Sqlite>,
+    issues: issue::service::Service<Sqlite>,
+    inbox: inbox::service::Service<InboxStore>,
+    search: search::service::Service<SearchIndex>,
+    /// Fetch attempts of the profile's repositories.
+    fetches: FetchLog,
+}
+
+impl Session {
+    pub fn patches(&self) -> patch::service::Service<Sqlite> {
+        self.services().patches
+    }
+
+    pub fn issues(&self) -> issue::service::Service<Sqlite> {
+        self.services().issues
+    }
+
+    pub fn inbox(&self) -> inbox::service::Service<InboxStore> {
+        self.services().inbox
+    }
+
+    pub fn search(&self) -> search::service::Service<SearchIndex> {
+        self.services().search
+    }
+
+    pub fn fetches(&self) -> FetchLog {
+        self.services().fetches
+    }
+
+    fn services(&self) -> Services {
+        self.services
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Open `profile`'s databases and make it the profile every command works
+/// with, replacing the one in use, if any.
+pub(crate) fn open(app: &AppHandle, profile: radicle::Profile) -> Result<Config, Error> {
+    let home = profile.home();
+
+    let cobs_cache = radicle::cob::cache::Store::open(home.cobs().join(COBS_DB_FILE))?;
+    cobs_cache.check_version()?;
+
+    let inbox_db = Sqlite::reader(profile.node().join(NOTIFICATIONS_DB_FILE))?;
+    let cobs_db = Sqlite::reader(profile.cobs().join(COBS_DB_FILE))?;
+
+    let data_dir = data_dir(app, &profile)?;
+    let inbox =
+        inbox::service::Service::new(InboxStore::open(data_dir.join(INBOX_DB_FILE), inbox_db)?);
+    let search = search::service::Service::new(SearchIndex::open(
+        data_dir.join(SEARCH_DB_FILE),
+        cobs_db.clone(),
+    )?);
+    let services = Services {
+        patches: patch::service::Service::new(cobs_db.clone()),
+        issues: issue::service::Service::new(cobs_db.clone()),
+        inbox: inbox.clone(),
+        search,
+        fetches: FetchLog::default(),
+    };
+    let stopped = Arc::new(AtomicBool::new(false));
+
+    match app.try_state::<Session>() {
+        Some(session) => {
+            *session
+                .services
+                .write()
+                .unwrap_or_else(PoisonError::into_inner) = services;
+            let previous = std::mem::replace(
+                &mut *session
+                    .stopped
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner),
+                stopped.clone(),
+            );
+            previous.store(true, Ordering::Relaxed);
+        }
+        None => {
+            app.manage(Session {
+                services: RwLock::new(services),
+                stopped: RwLock::new(stopped.clone()),
+            });
+        }
+    }
+
+    let watcher_handle = app.app_handle().clone();
+    let watcher_node = Node::new(profile.home().socket_from_env());
+    let watcher_stopped = stopped.clone();
+    std::thread::spawn(move || {
+        crate::watcher::watch(
+            watcher_handle,
+            watcher_node,
+            inbox,
+            cobs_db,
+            watcher_stopped,
+        )
+    });
+
+    let node_handle = app.app_handle().clone();
+    let node = Node::new(profile.home().socket_from_env());
+    tauri::async_runtime::spawn(async move {
+        while !stopped.load(Ordering::Relaxed) {
+            let _ = node_handle.emit("node_running", node.is_running());
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        }
+    });
+
+    let state = match app.try_state::<AppState>() {
+        Some(state) => {
+            state.set_profile(profile);
+            (*state).clone()
+        }
+        None => {
+            let state = AppState::new(profile);
+            app.manage(state.clone());
+            state
+        }
+    };
+
+    Ok(state.config())
+}
+
+/// The app's own data dir for `profile`. Each profile gets a directory named
+/// after its NID; databases from before there were several profiles are
+/// moved into the directory of the first profile opened.
+fn data_dir(app: &AppHandle, profile: &radicle::Profile) -> Result<PathBuf, Error> {
+    let root = app.path().app_data_dir()?;
+    let dir = root.join(profile.public_key.to_string());
+
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir)?;
+        for file in [INBOX_DB_FILE, SEARCH_DB_FILE] {
+            let legacy = root.join(file);
+            if legacy.exists() {
+                std::fs::rename(legacy, dir.join(file))?;
+            }
+        }
+    }
+
+    Ok(dir)
+}
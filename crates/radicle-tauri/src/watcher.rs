@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time;
+
+use radicle::node::{self, Handle, Node};
+use tauri::{AppHandle, Emitter};
+
+use radicle_types::domain::inbox::service::Service;
+use radicle_types::domain::inbox::traits::InboxService;
+use radicle_types::events::{Event, Snapshot};
+use radicle_types::outbound::inbox::InboxStore;
+use radicle_types::outbound::sqlite::Sqlite;
+
+/// How long the node's event stream may stay silent before it is re-opened.
+/// Also bounds how long a stopped node goes unnoticed.
+const SUBSCRIBE_TIMEOUT: time::Duration = time::Duration::from_secs(30);
+
+/// How often the COB cache and the inbox are checked for changes.
+const POLL_INTERVAL: time::Duration = time::Duration::from_secs(2);
+
+/// Forward changes to storage to the frontend as `radicle_event`s: fetches
+/// from the node's event stream while it runs, and writes to the COB cache by
+/// comparing snapshots of it. The cache is watched whether or not the node
+/// runs, since `rad` writes to it without going through the node. Only
+/// returns once `stopped` is set, so it needs a thread of its own.
+pub(crate) fn watch(
+    app: AppHandle,
+    node: Node,
+    inbox: Service<InboxStore>,
+    cobs: Sqlite,
+    stopped: Arc<AtomicBool>,
+) {
+    let poller = {
+        let app = app.clone();
+        let stopped = stopped.clone();
+        std::thread::spawn(move || poll(app, inbox, cobs, stopped))
+    };
+
+    while !stopped.load(Ordering::Relaxed) {
+        if !node.is_running() {
+            std::thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        let events = match node.subscribe(SUBSCRIBE_TIMEOUT) {
+            Ok(events) => events,
+            Err(e) => {
+                log::warn!("Not able to subscribe to node events: {e}");
+                std::thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+        };
+        // The stream ends in an error on timeout or when the node stops,
+        // either way the outer loop decides how to carry on.
+        for event in events {
+            if stopped.load(Ordering::Relaxed) {
+                break;
+            }
+            match event {
+                Ok(node::Event::RefsFetched {
+                    remote,
+                    rid,
+                    updated,
+                }) => Event::from_fetch(rid, remote, &updated)
+                    .into_iter()
+                    .for_each(|event| emit(&app, event)),
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+    }
+
+    if poller.join().is_err() {
+        log::error!("The COB cache watcher panicked");
+    }
+}
+
+/// Diff the COB cache and check the inbox every [`POLL_INTERVAL`] until
+/// `stopped` is set. A patch or issue changed by a fetch is reported by both
+/// this and the node's event stream, which only costs views a second refresh.
+fn poll(app: AppHandle, inbox: Service<InboxStore>, cobs: Sqlite, stopped: Arc<AtomicBool>) {
+    let mut notifications = inbox.notification_count().unwrap_or_default();
+    let mut snapshot: Option<Snapshot> = None;
+
+    while !stopped.load(Ordering::Relaxed) {
+        match snapshot.as_mut() {
+            Some(snapshot) => match snapshot.update(&cobs) {
+                Ok(events) => events.into_iter().for_each(|event| emit(&app, event)),
+                Err(e) => log::warn!("Failed to read the COB cache: {e}"),
+            },
+            None => snapshot = Snapshot::new(&cobs).ok(),
+        }
+        check_notifications(&app, &inbox, &mut notifications);
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn emit(app: &AppHandle, event: Event) {
+    if let Err(e) = app.emit("radicle_event", event) {
+        log::error!("Failed to emit event: {e}");
+    }
+}
+
+/// Emit `NotificationCreated` when the unread count went up since `last`.
+fn check_notifications(app: &AppHandle, inbox: &Service<InboxStore>, last: &mut usize) {
+    let Ok(count) = inbox.notification_count() else {
+        return;
+    };
+    if count > *last {
+        emit(app, Event::NotificationCreated { count });
+    }
+    *last = count;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Facets } from "./Facets";
+
+/**
+ * State counts of one COB kind together with its label and assignee
+ * facets.
+ */
+export type FacetedCounts<C> = { counts: C; facets: Facets };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AssigneeFacet } from "./AssigneeFacet";
+import type { LabelFacet } from "./LabelFacet";
+
+/**
+ * How many items carry each label and are assigned to each person, most
+ * common first.
+ */
+export type Facets = {
+  labels: Array<LabelFacet>;
+  assignees: Array<AssigneeFacet>;
+};
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Labels an item must carry: at least one of them, or all of them.
+ */
+export type LabelFilter = { "any": Array<string> } | { "all": Array<string> };
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LabelFilter } from "./LabelFilter";
+import type { Order } from "./Order";
+import type { Sort } from "./Sort";
+
+/**
+ * Narrows and orders an issue or patch list beyond its status. Every
+ * filter that is set must match; the default lists newest first.
+ */
+export type ListFilter = {
+  labels?: LabelFilter;
+  author?: string;
+  assignee?: string;
+  /**
+   * Someone who reviewed any revision. Only applies to patches.
+   */
+  reviewer?: string;
+  /**
+   * Only items with activity at or after this time, in milliseconds.
+   */
+  updatedSince?: number;
+  sort: Sort;
+  order: Order;
+};
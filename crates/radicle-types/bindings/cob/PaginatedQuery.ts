@@ -1,3 +1,12 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type PaginatedQuery<T> = { cursor: number; more: boolean; content: T };
+export type PaginatedQuery<T> = {
+  cursor: number;
+  more: boolean;
+  content: T;
+  /**
+   * Where the next page starts; absent on the last page. Unlike `cursor`,
+   * it stays valid when items are added or reordered between requests.
+   */
+  next?: string;
+};
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What to order by. Last activity is the latest comment, edit,
+ * revision or review.
+ */
+export type Sort = "created" | "lastActivity" | "commentCount";
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A delegate's verdict on a pending revision.
+ */
+export type Action =
+  | { "type": "accept"; revision: string }
+  | { "type": "reject"; revision: string };
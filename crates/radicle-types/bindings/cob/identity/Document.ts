@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProjectPayloadData } from "../../repo/ProjectPayloadData";
+import type { Visibility } from "../../repo/Visibility";
+import type { Author } from "../Author";
+
+/**
+ * The parts of an identity document the app shows and lets delegates change.
+ */
+export type Document = {
+  delegates: Array<Author>;
+  threshold: number;
+  visibility: Visibility;
+  project?: ProjectPayloadData;
+};
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Visibility } from "../../repo/Visibility";
+import type { Author } from "../Author";
+import type { Change } from "./Change";
+
+/**
+ * What changed from one version of an identity document to another. Fields
+ * that didn't change are left out.
+ */
+export type DocumentDiff = {
+  addedDelegates: Array<Author>;
+  removedDelegates: Array<Author>;
+  threshold?: Change<number>;
+  visibility?: Change<Visibility>;
+  name?: Change<string>;
+  description?: Change<string>;
+  defaultBranch?: Change<string>;
+};
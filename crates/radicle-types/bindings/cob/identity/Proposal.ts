@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Visibility } from "../../repo/Visibility";
+
+/**
+ * Changes to propose to the identity document. Fields that are not set keep
+ * their current value.
+ */
+export type Proposal = {
+  title: string;
+  description?: string;
+  addDelegates?: Array<string>;
+  removeDelegates?: Array<string>;
+  threshold?: number;
+  visibility?: Visibility;
+  /**
+   * The new description of the project.
+   */
+  projectDescription?: string;
+  defaultBranch?: string;
+};
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Author } from "../Author";
+import type { Document } from "./Document";
+import type { State } from "./State";
+
+/**
+ * A version of the identity document, as proposed in a revision of the
+ * `xyz.radicle.id` COB.
+ */
+export type Revision = {
+  id: string;
+  parent?: string;
+  author: Author;
+  title: string;
+  description: string;
+  state: State;
+  /**
+   * Whether this is the revision the repository currently uses.
+   */
+  current: boolean;
+  document: Document;
+  accepted: Array<Author>;
+  rejected: Array<Author>;
+  timestamp: number;
+};
@@ -2,6 +2,7 @@
 import type { Action } from "../issue/Action";
 import type { State } from "../issue/State";
 import type { ActionWithAuthor } from "./ActionWithAuthor";
+import type { NotificationState } from "./NotificationState";
 import type { RefUpdate } from "./RefUpdate";
 
 export type Issue = {
@@ -12,5 +13,6 @@ export type Issue = {
   timestamp: number;
   status: State;
   actions: Array<ActionWithAuthor<Action>>;
+  state: NotificationState;
   repoId: string;
 };
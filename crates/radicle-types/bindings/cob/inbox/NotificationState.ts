@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where a notification stands, as tracked by the app.
+ *
+ * Notifications start out unread. A snoozed notification turns unread again
+ * once its snooze time has passed, and a done one is hidden until the node
+ * records new activity on the same object.
+ */
+export type NotificationState = "unread" | "read" | "snoozed" | "done";
@@ -2,6 +2,7 @@
 import type { Action } from "../patch/Action";
 import type { State } from "../patch/State";
 import type { ActionWithAuthor } from "./ActionWithAuthor";
+import type { NotificationState } from "./NotificationState";
 import type { RefUpdate } from "./RefUpdate";
 
 export type Patch = {
@@ -12,5 +13,6 @@ export type Patch = {
   title: string;
   status: State;
   actions: Array<ActionWithAuthor<Action>>;
+  state: NotificationState;
   repoId: string;
 };
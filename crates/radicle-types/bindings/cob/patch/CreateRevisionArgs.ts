@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A new revision for an existing patch. The head is `sha` when given,
+ * otherwise `branch` resolved under `peer` the way the source browser does.
+ */
+export type CreateRevisionArgs = {
+  rid: string;
+  patchId: string;
+  sha?: string;
+  peer?: string;
+  branch?: string;
+  description: string;
+};
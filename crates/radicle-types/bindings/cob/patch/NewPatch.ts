@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Embed } from "../thread/Embed";
+
+/**
+ * A patch to open. Its head is `sha` when given, otherwise `branch` resolved
+ * under `peer` the way the source browser does.
+ */
+export type NewPatch = {
+  title: string;
+  description: string;
+  sha?: string;
+  peer?: string;
+  branch?: string;
+  target?: string;
+  labels?: Array<string>;
+  draft?: boolean;
+  embeds?: Array<Embed>;
+};
@@ -27,4 +27,16 @@ export type Config = {
    * explorer links.
    */
   preferredSeeds: Array<string>;
+  /**
+   * Addresses the node listens on for inbound connections.
+   */
+  listen: Array<string>;
+  /**
+   * Peers the node stays connected to.
+   */
+  connect: Array<string>;
+  /**
+   * Addresses other nodes are told to reach us on.
+   */
+  externalAddresses: Array<string>;
 };
@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Changes to the profile configuration. Settings left out are kept as they
+ * are.
+ */
+export type ConfigEdit = {
+  alias?: string;
+  seedingPolicy?: { default: "allow"; scope: "followed" | "all" } | {
+    default: "block";
+  };
+  publicExplorer?: string;
+  preferredSeeds?: Array<string>;
+  listen?: Array<string>;
+  connect?: Array<string>;
+  externalAddresses?: Array<string>;
+};
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Config } from "./Config";
+import type { NodeSetting } from "./NodeSetting";
+
+/**
+ * The outcome of editing the configuration.
+ */
+export type ConfigUpdate = {
+  config: Config;
+  /**
+   * Changed settings the node only picks up when it restarts.
+   */
+  restart: Array<NodeSetting>;
+};
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CobKind } from "./CobKind";
+
+export type Problem = { "type": "unreadable"; reason: string } | {
+  "type": "invalidSignedRefs";
+  remote: string;
+  reason: string;
+} | { "type": "needsMigration"; remote: string } | {
+  "type": "danglingCobRef";
+  reference: string;
+} | {
+  "type": "cacheMismatch";
+  cob: CobKind;
+  /**
+   * Objects in storage that aren't cached.
+   */
+  missing: number;
+  /**
+   * Cached objects that aren't in storage.
+   */
+  stale: number;
+};
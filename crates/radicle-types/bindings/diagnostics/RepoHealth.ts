@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Problem } from "./Problem";
+import type { Repair } from "./Repair";
+
+export type RepoHealth = {
+  rid: string;
+  name?: string;
+  problems: Array<Problem>;
+  /**
+   * Actions that should fix the problems, in the order to try them.
+   */
+  repairs: Array<Repair>;
+};
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CacheHealth } from "./CacheHealth";
+import type { Repair } from "./Repair";
+import type { RepoHealth } from "./RepoHealth";
+
+/**
+ * The outcome of checking local storage and the COB cache.
+ */
+export type Report = {
+  cache: CacheHealth;
+  /**
+   * How many repositories were checked.
+   */
+  checked: number;
+  /**
+   * The repositories with at least one problem.
+   */
+  repos: Array<RepoHealth>;
+  /**
+   * Actions that should make the cache usable again, for every repository
+   * at once.
+   */
+  repairs: Array<Repair>;
+};
@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A change to local state, pushed to the frontend as a `radicle_event` so
+ * open views can refresh what changed instead of refetching blindly.
+ */
+export type Event =
+  | { "event": "patchUpdated"; "data": { rid: string; id: string } }
+  | { "event": "issueUpdated"; "data": { rid: string; id: string } }
+  | { "event": "refsFetched"; "data": { rid: string; remote: string } }
+  | {
+    "event": "notificationCreated";
+    /**
+     * The inbox grew; `count` is the new unread total.
+     */
+    "data": { count: number };
+  };
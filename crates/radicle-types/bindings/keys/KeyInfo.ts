@@ -0,0 +1,17 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The profile's key pair, as far as it's safe to show.
+ */
+export type KeyInfo = {
+  did: string;
+  nid: string;
+  /**
+   * The SHA-256 fingerprint, the way `ssh-keygen -l` prints it.
+   */
+  fingerprint: string;
+  /**
+   * Whether the secret key is protected by a passphrase.
+   */
+  encrypted: boolean;
+};
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The network settings the node runs with.
+ */
+export type Config = {
+  listen: Array<string>;
+  /**
+   * Addresses other nodes are told to reach us on.
+   */
+  externalAddresses: Array<string>;
+  /**
+   * Tor settings, as they appear in the node configuration.
+   */
+  onion?: unknown;
+  /**
+   * I2P settings, as they appear in the node configuration.
+   */
+  i2p?: unknown;
+};
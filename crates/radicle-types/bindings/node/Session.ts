@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Link } from "./Link";
+import type { SessionState } from "./SessionState";
+
+/**
+ * A connection to another node.
+ */
+export type Session = {
+  nid: string;
+  alias?: string;
+  addr: string;
+  link: Link;
+  state: SessionState;
+};
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Config } from "./Config";
+import type { Session } from "./Session";
+
+/**
+ * What the node is up to, as far as its control socket tells.
+ */
+export type Status = { "status": "stopped" } | {
+  "status": "running";
+  sessions: Array<Session>;
+  config: Config;
+  /**
+   * How many (repository, seed) pairs the routing table holds.
+   */
+  routingEntries: number;
+  /**
+   * The repositories we announce to the network.
+   */
+  inventory: Array<string>;
+};
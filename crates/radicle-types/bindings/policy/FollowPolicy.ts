@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Policy } from "./Policy";
+
+/**
+ * A peer we follow or block.
+ */
+export type FollowPolicy = {
+  nid: string;
+  /**
+   * The alias given when following, or else the one the peer announced.
+   */
+  alias?: string;
+  policy: Policy;
+};
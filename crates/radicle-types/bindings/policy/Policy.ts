@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether we fetch from a peer or repository, or refuse to.
+ */
+export type Policy = "allow" | "block";
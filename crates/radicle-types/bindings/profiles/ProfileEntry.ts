@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A Radicle home the app knows about.
+ */
+export type ProfileEntry = {
+  home: string;
+  active: boolean;
+  nid?: string;
+  alias?: string;
+  /**
+   * Why the profile can't be loaded, e.g. because its home was moved.
+   */
+  error?: string;
+};
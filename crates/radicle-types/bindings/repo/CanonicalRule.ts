@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Author } from "../cob/Author";
+import type { RefQuorum } from "./RefQuorum";
+
+/**
+ * A canonical-refs rule from the identity document, with the quorum of every
+ * ref it covers.
+ */
+export type CanonicalRule = {
+  /**
+   * Ref pattern the rule applies to, e.g. `refs/tags/*`.
+   */
+  pattern: string;
+  /**
+   * The peers whose refs count as votes.
+   */
+  allowed: Array<Author>;
+  threshold: number;
+  refs: Array<RefQuorum>;
+};
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RepoUsage } from "./RepoUsage";
+
+/**
+ * How much disk space the repositories in storage take.
+ */
+export type DiskUsage = {
+  /**
+   * In bytes.
+   */
+  total: number;
+  /**
+   * Largest first.
+   */
+  repos: Array<RepoUsage>;
+};
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FetchOutcome } from "./FetchOutcome";
+
+export type FetchAttempt = {
+  /**
+   * Unix time in milliseconds.
+   */
+  timestamp: number;
+  outcome: FetchOutcome;
+};
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Scope } from "./Scope";
+import type { Visibility } from "./Visibility";
+
+/**
+ * A git working copy to turn into a Radicle repository.
+ */
+export type InitRepo = {
+  path: string;
+  name: string;
+  description: string;
+  defaultBranch: string;
+  visibility?: Visibility;
+  /**
+   * Seed the new repository, and announce it if it's public.
+   */
+  seed?: boolean;
+  /**
+   * The peers to fetch the repository from once it's seeded. Defaults to
+   * the scope of the profile's default seeding policy.
+   */
+  scope?: Scope;
+};
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FetchAttempt } from "./FetchAttempt";
+import type { Provider } from "./Provider";
+
+/**
+ * A repository we seed but don't have in storage yet, with what is known
+ * about why.
+ */
+export type PendingRepo = {
+  rid: string;
+  /**
+   * Seeds that announced the repository, according to the routing table.
+   */
+  providers: Array<Provider>;
+  /**
+   * The last time the app tried to fetch it, if it did since it started.
+   */
+  lastAttempt?: FetchAttempt;
+};
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Quorum } from "./Quorum";
+import type { Vote } from "./Vote";
+
+/**
+ * How the allowed peers vote on a single canonical ref.
+ */
+export type RefQuorum = {
+  /**
+   * Fully qualified ref name, e.g. `refs/heads/main`.
+   */
+  name: string;
+  /**
+   * Where the canonical ref currently points, if it exists.
+   */
+  canonical?: string;
+  votes: Array<Vote>;
+  quorum: Quorum;
+};
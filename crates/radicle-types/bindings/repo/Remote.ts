@@ -5,6 +5,10 @@ export type Remote = {
   id: string;
   alias?: string;
   delegate: boolean;
+  /**
+   * Whether we follow this peer.
+   */
+  followed: boolean;
   branches: { [key in string]: string };
   tags: { [key in string]: Tag };
 };
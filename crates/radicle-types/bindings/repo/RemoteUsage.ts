@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A remote's namespace in a repository. Sizes are in bytes.
+ */
+export type RemoteUsage = {
+  nid: string;
+  alias?: string;
+  /**
+   * How many refs the remote has in its namespace.
+   */
+  refs: number;
+  /**
+   * Objects reachable from the remote's refs. Objects are shared between
+   * remotes, so these add up to more than the repository takes. Only
+   * counted when a single repository is asked for.
+   */
+  size?: number;
+  /**
+   * Objects reachable from no other remote's refs, which is about what
+   * dropping the remote would free once the repository is pruned. Only
+   * counted along with `size`.
+   */
+  unique?: number;
+};
@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RemoteUsage } from "./RemoteUsage";
+
+/**
+ * Sizes are in bytes.
+ */
+export type RepoUsage = {
+  rid: string;
+  name?: string;
+  /**
+   * Everything in the repository's directory.
+   */
+  total: number;
+  looseObjects: number;
+  packs: number;
+  remotes: Array<RemoteUsage>;
+  /**
+   * Why the repository couldn't be read. Only `total` is known then.
+   */
+  error?: string;
+};
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Scope } from "./Scope";
+
+/**
+ * A repository we seed.
+ */
+export type SeedPolicy = {
+  rid: string;
+  /**
+   * The project name, once the repository is in storage.
+   */
+  name?: string;
+  scope: Scope;
+  /**
+   * Whether the repository has been fetched into storage yet.
+   */
+  replicated: boolean;
+};
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SyncStatus } from "./SyncStatus";
+
+/**
+ * Where a seed stands with our signed refs of a repository.
+ */
+export type SeedSync = { nid: string; alias?: string; status: SyncStatus };
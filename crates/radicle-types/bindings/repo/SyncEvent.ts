@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Progress of syncing a repository with its seeds.
+ */
+export type SyncEvent = {
+  "type": "fetched";
+  nid: string;
+  updated: number;
+} | { "type": "fetchFailed"; nid: string; reason: string } | {
+  "type": "announced";
+} | { "type": "synced"; nid: string; at: string };
@@ -17,4 +17,10 @@ export type SyncStatus = {
    * Remote head of our `rad/sigrefs`.
    */
   remote: SyncedAt;
+} | {
+  "status": "unpublished";
+  /**
+   * Remote head of our `rad/sigrefs`, from when we still had them.
+   */
+  remote: SyncedAt;
 };
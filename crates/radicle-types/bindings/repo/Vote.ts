@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Author } from "../cob/Author";
+
+export type Vote = {
+  peer: Author;
+  /**
+   * The commit, or tag object, the peer has the ref at. Absent if the
+   * peer doesn't have the ref.
+   */
+  oid?: string;
+};
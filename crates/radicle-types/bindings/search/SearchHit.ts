@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Author } from "../cob/Author";
+import type { Kind } from "./Kind";
+
+/**
+ * A ranked search result. `entry` points at the comment, revision or review
+ * the match is in, and is absent when the title matched. Matched terms in
+ * `snippet` are wrapped in `<mark>` tags; the rest is the text as written.
+ */
+export type SearchHit = {
+  kind: Kind;
+  rid: string;
+  id: string;
+  entry?: string;
+  author: Author;
+  title: string;
+  snippet: string;
+};
@@ -4,11 +4,12 @@ use radicle::profile::Aliases;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use radicle::Did;
 use radicle::cob;
-use radicle::identity;
 use radicle::node::{Alias, AliasStore};
 
 pub mod diff;
+pub mod identity;
 pub mod issue;
 pub mod job;
 pub mod repo;
@@ -44,25 +45,93 @@ pub enum CacheEvent {
 #[ts(export_to = "cob/")]
 pub struct Author {
     #[ts(as = "String")]
-    did: identity::Did,
+    did: Did,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[ts(as = "Option<String>", optional)]
     alias: Option<Alias>,
 }
 
 impl Author {
-    pub fn new(did: &identity::Did, aliases: &impl AliasStore) -> Self {
+    pub fn new(did: &Did, aliases: &impl AliasStore) -> Self {
         Self {
             did: *did,
             alias: aliases.alias(did),
         }
     }
 
-    pub fn did(&self) -> &identity::Did {
+    pub fn did(&self) -> &Did {
         &self.did
     }
 }
 
+/// State counts of one COB kind together with its label and assignee
+/// facets.
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "cob/")]
+pub struct FacetedCounts<C> {
+    pub counts: C,
+    pub facets: Facets,
+}
+
+/// How many items carry each label and are assigned to each person, most
+/// common first.
+#[derive(Debug, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "cob/")]
+pub struct Facets {
+    pub labels: Vec<LabelFacet>,
+    pub assignees: Vec<AssigneeFacet>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "cob/")]
+pub struct LabelFacet {
+    #[ts(as = "String")]
+    pub label: cob::Label,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "cob/")]
+pub struct AssigneeFacet {
+    pub assignee: Author,
+    pub count: usize,
+}
+
+/// Facets as stored, before assignees are resolved to their aliases.
+#[derive(Debug, Default)]
+pub struct FacetCounts {
+    pub labels: Vec<(cob::Label, usize)>,
+    pub assignees: Vec<(Did, usize)>,
+}
+
+impl FacetCounts {
+    pub fn resolve(self, aliases: &impl AliasStore) -> Facets {
+        Facets {
+            labels: self
+                .labels
+                .into_iter()
+                .map(|(label, count)| LabelFacet { label, count })
+                .collect(),
+            assignees: self
+                .assignees
+                .into_iter()
+                .map(|(did, count)| AssigneeFacet {
+                    assignee: Author::new(&did, aliases),
+                    count,
+                })
+                .collect(),
+        }
+    }
+}
+
 pub trait FromRadicleAction<A> {
     fn from_radicle_action(value: A, aliases: &Aliases) -> Self;
 }
@@ -120,11 +189,64 @@ pub struct PaginatedQuery<T> {
     pub cursor: usize,
     pub more: bool,
     pub content: T,
+    /// Where the next page starts; absent on the last page. Unlike `cursor`,
+    /// it stays valid when items are added or reordered between requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub next: Option<Cursor>,
+}
+
+/// The position of an item in a sorted list: its sort key and id, the same
+/// pair lists are ordered by. Clients treat it as an opaque string and pass
+/// it back to resume a list right after that item.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(into = "String", try_from = "String")]
+pub struct Cursor {
+    pub key: i64,
+    pub id: String,
+}
+
+impl std::fmt::Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.key, self.id)
+    }
+}
+
+impl std::str::FromStr for Cursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, id) = s
+            .split_once('.')
+            .ok_or_else(|| anyhow::anyhow!("invalid cursor `{s}`"))?;
+
+        Ok(Self {
+            key: key.parse()?,
+            id: id.to_owned(),
+        })
+    }
+}
+
+impl From<Cursor> for String {
+    fn from(cursor: Cursor) -> Self {
+        cursor.to_string()
+    }
+}
+
+impl TryFrom<String> for Cursor {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
 }
 
 pub mod query {
     use serde::{Deserialize, Serialize};
+    use ts_rs::TS;
 
+    use radicle::Did;
+    use radicle::cob::Label;
     use radicle::issue;
     use radicle::patch;
 
@@ -177,4 +299,76 @@ pub mod query {
             }
         }
     }
+
+    /// Narrows and orders an issue or patch list beyond its status. Every
+    /// filter that is set must match; the default lists newest first.
+    #[derive(Debug, Default, Clone, Serialize, Deserialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    #[ts(export_to = "cob/")]
+    pub struct ListFilter {
+        #[serde(default)]
+        #[ts(optional)]
+        pub labels: Option<LabelFilter>,
+        #[serde(default)]
+        #[ts(as = "Option<String>", optional)]
+        pub author: Option<Did>,
+        #[serde(default)]
+        #[ts(as = "Option<String>", optional)]
+        pub assignee: Option<Did>,
+        /// Someone who reviewed any revision. Only applies to patches.
+        #[serde(default)]
+        #[ts(as = "Option<String>", optional)]
+        pub reviewer: Option<Did>,
+        /// Only items with activity at or after this time, in milliseconds.
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub updated_since: Option<u64>,
+        #[serde(default)]
+        pub sort: Sort,
+        #[serde(default)]
+        pub order: Order,
+    }
+
+    /// Labels an item must carry: at least one of them, or all of them.
+    #[derive(Debug, Clone, Serialize, Deserialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    #[ts(export_to = "cob/")]
+    pub enum LabelFilter {
+        Any(#[ts(as = "Vec<String>")] Vec<Label>),
+        All(#[ts(as = "Vec<String>")] Vec<Label>),
+    }
+
+    /// What to order by. Last activity is the latest comment, edit,
+    /// revision or review.
+    #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    #[ts(export_to = "cob/")]
+    pub enum Sort {
+        #[default]
+        Created,
+        LastActivity,
+        CommentCount,
+    }
+
+    #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    #[ts(export_to = "cob/")]
+    pub enum Order {
+        Asc,
+        #[default]
+        Desc,
+    }
+
+    impl Order {
+        pub fn as_sql(&self) -> &'static str {
+            match self {
+                Self::Asc => "ASC",
+                Self::Desc => "DESC",
+            }
+        }
+    }
 }
@@ -0,0 +1,249 @@
+use std::collections::BTreeSet;
+
+use radicle::cob::identity;
+use radicle::node::AliasStore;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use radicle::cob;
+use radicle::identity::{Did, Doc, doc};
+
+use crate::cobs;
+use crate::repo;
+
+/// A version of the identity document, as proposed in a revision of the
+/// `xyz.radicle.id` COB.
+#[derive(TS, Serialize)]
+#[ts(export)]
+#[ts(export_to = "cob/identity/")]
+#[serde(rename_all = "camelCase")]
+pub struct Revision {
+    #[ts(as = "String")]
+    pub id: identity::RevisionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub parent: Option<identity::RevisionId>,
+    pub author: cobs::Author,
+    pub title: String,
+    pub description: String,
+    pub state: State,
+    /// Whether this is the revision the repository currently uses.
+    pub current: bool,
+    pub document: Document,
+    pub accepted: Vec<cobs::Author>,
+    pub rejected: Vec<cobs::Author>,
+    #[ts(type = "number")]
+    pub timestamp: cob::Timestamp,
+}
+
+impl Revision {
+    pub fn new(
+        revision: &identity::Revision,
+        current: &identity::RevisionId,
+        aliases: &impl AliasStore,
+    ) -> Self {
+        Self {
+            id: revision.id,
+            parent: revision.parent,
+            author: cobs::Author::new(revision.author.id(), aliases),
+            title: revision.title.to_string(),
+            description: revision.description.to_string(),
+            state: revision.state.into(),
+            current: revision.id == *current,
+            document: Document::new(revision.doc(), aliases),
+            accepted: revision
+                .accepted()
+                .map(|did| cobs::Author::new(&did, aliases))
+                .collect(),
+            rejected: revision
+                .rejected()
+                .map(|did| cobs::Author::new(&did, aliases))
+                .collect(),
+            timestamp: revision.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TS, Serialize, Deserialize)]
+#[ts(export)]
+#[ts(export_to = "cob/identity/")]
+#[serde(rename_all = "camelCase")]
+pub enum State {
+    /// Waiting for enough delegates to accept it.
+    Active,
+    Accepted,
+    Rejected,
+    /// Superseded by another revision before it was accepted.
+    Stale,
+}
+
+impl From<identity::State> for State {
+    fn from(value: identity::State) -> Self {
+        match value {
+            identity::State::Active => Self::Active,
+            identity::State::Accepted => Self::Accepted,
+            identity::State::Rejected => Self::Rejected,
+            identity::State::Stale => Self::Stale,
+        }
+    }
+}
+
+/// The parts of an identity document the app shows and lets delegates change.
+#[derive(TS, Serialize)]
+#[ts(export)]
+#[ts(export_to = "cob/identity/")]
+#[serde(rename_all = "camelCase")]
+pub struct Document {
+    pub delegates: Vec<cobs::Author>,
+    pub threshold: usize,
+    pub visibility: repo::Visibility,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub project: Option<repo::ProjectPayloadData>,
+}
+
+impl Document {
+    pub fn new(doc: &Doc, aliases: &impl AliasStore) -> Self {
+        Self {
+            delegates: doc
+                .delegates()
+                .iter()
+                .map(|did| cobs::Author::new(did, aliases))
+                .collect(),
+            threshold: doc.threshold(),
+            visibility: repo::Visibility::new(doc.visibility(), aliases),
+            project: doc
+                .payload()
+                .get(&doc::PayloadId::project())
+                .and_then(|payload| (*payload).clone().try_into().ok()),
+        }
+    }
+}
+
+/// What changed from one version of an identity document to another. Fields
+/// that didn't change are left out.
+#[derive(TS, Serialize)]
+#[ts(export)]
+#[ts(export_to = "cob/identity/")]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDiff {
+    pub added_delegates: Vec<cobs::Author>,
+    pub removed_delegates: Vec<cobs::Author>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub threshold: Option<Change<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub visibility: Option<Change<repo::Visibility>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub name: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<Change<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub default_branch: Option<Change<String>>,
+}
+
+impl DocumentDiff {
+    pub fn new(old: &Doc, new: &Doc, aliases: &impl AliasStore) -> Self {
+        let old_delegates = old.delegates().iter().collect::<BTreeSet<_>>();
+        let new_delegates = new.delegates().iter().collect::<BTreeSet<_>>();
+        let (old_project, new_project) = (
+            Document::new(old, aliases).project,
+            Document::new(new, aliases).project,
+        );
+        let project = |field: fn(&repo::ProjectPayloadData) -> &String| {
+            Change::new(
+                old_project.as_ref().map(field).cloned().unwrap_or_default(),
+                new_project.as_ref().map(field).cloned().unwrap_or_default(),
+            )
+        };
+
+        Self {
+            added_delegates: new_delegates
+                .difference(&old_delegates)
+                .map(|did| cobs::Author::new(did, aliases))
+                .collect(),
+            removed_delegates: old_delegates
+                .difference(&new_delegates)
+                .map(|did| cobs::Author::new(did, aliases))
+                .collect(),
+            threshold: Change::new(old.threshold(), new.threshold()),
+            visibility: (old.visibility() != new.visibility()).then(|| Change {
+                old: repo::Visibility::new(old.visibility(), aliases),
+                new: repo::Visibility::new(new.visibility(), aliases),
+            }),
+            name: project(|p| &p.name),
+            description: project(|p| &p.description),
+            default_branch: project(|p| &p.default_branch),
+        }
+    }
+}
+
+#[derive(TS, Serialize)]
+#[ts(export)]
+#[ts(export_to = "cob/identity/")]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    /// A change from `old` to `new`, if they differ.
+    fn new(old: T, new: T) -> Option<Self> {
+        (old != new).then_some(Self { old, new })
+    }
+}
+
+/// Changes to propose to the identity document. Fields that are not set keep
+/// their current value.
+#[derive(TS, Deserialize)]
+#[ts(export)]
+#[ts(export_to = "cob/identity/")]
+#[serde(rename_all = "camelCase")]
+pub struct Proposal {
+    pub title: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub description: String,
+    #[serde(default)]
+    #[ts(as = "Option<Vec<String>>", optional)]
+    pub add_delegates: Vec<Did>,
+    #[serde(default)]
+    #[ts(as = "Option<Vec<String>>", optional)]
+    pub remove_delegates: Vec<Did>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub threshold: Option<usize>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub visibility: Option<repo::Visibility>,
+    /// The new description of the project.
+    #[serde(default)]
+    #[ts(optional)]
+    pub project_description: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub default_branch: Option<String>,
+}
+
+/// A delegate's verdict on a pending revision.
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "cob/identity/")]
+pub enum Action {
+    #[serde(rename = "accept")]
+    Accept {
+        #[ts(as = "String")]
+        revision: identity::RevisionId,
+    },
+
+    #[serde(rename = "reject")]
+    Reject {
+        #[ts(as = "String")]
+        revision: identity::RevisionId,
+    },
+}
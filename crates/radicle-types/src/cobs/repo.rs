@@ -20,6 +20,13 @@ pub enum SyncStatus {
         /// Remote head of our `rad/sigrefs`.
         remote: SyncedAt,
     },
+    /// We have no `rad/sigrefs` of our own, e.g. because we only seed the
+    /// repository, so there is nothing of ours to sync.
+    #[serde(rename_all = "camelCase")]
+    Unpublished {
+        /// Remote head of our `rad/sigrefs`, from when we still had them.
+        remote: SyncedAt,
+    },
 }
 
 impl From<radicle::node::SyncStatus> for SyncStatus {
@@ -54,3 +61,48 @@ impl From<radicle::node::SyncedAt> for SyncedAt {
         }
     }
 }
+
+/// Where a seed stands with our signed refs of a repository.
+#[derive(Debug, Clone, serde::Serialize, ts_rs::TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct SeedSync {
+    #[ts(as = "String")]
+    pub nid: radicle::node::NodeId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub alias: Option<radicle::node::Alias>,
+    pub status: SyncStatus,
+}
+
+/// Progress of syncing a repository with its seeds.
+#[derive(Debug, Clone, serde::Serialize, ts_rs::TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub enum SyncEvent {
+    /// Fetched from a seed, updating this many refs.
+    #[serde(rename_all = "camelCase")]
+    Fetched {
+        #[ts(as = "String")]
+        nid: radicle::node::NodeId,
+        updated: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    FetchFailed {
+        #[ts(as = "String")]
+        nid: radicle::node::NodeId,
+        reason: String,
+    },
+    /// Our refs were announced to the network.
+    Announced,
+    /// A seed has our latest refs.
+    #[serde(rename_all = "camelCase")]
+    Synced {
+        #[ts(as = "String")]
+        nid: radicle::node::NodeId,
+        #[ts(as = "String")]
+        at: radicle::git::Oid,
+    },
+}
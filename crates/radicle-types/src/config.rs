@@ -1,10 +1,15 @@
+use std::net;
+use std::str::FromStr;
+
 use radicle::crypto::PublicKey;
 use radicle::explorer::Explorer;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use radicle::node::Alias;
 use radicle::node::config::{ConnectAddress, DefaultSeedingPolicy};
+use radicle::node::{Address, Alias};
+
+use crate::error::Error;
 
 /// Service configuration.
 #[derive(Debug, TS, Serialize, PartialEq)]
@@ -29,6 +34,134 @@ pub struct Config {
     /// explorer links.
     #[ts(as = "Vec<String>")]
     pub preferred_seeds: Vec<ConnectAddress>,
+    /// Addresses the node listens on for inbound connections.
+    #[ts(as = "Vec<String>")]
+    pub listen: Vec<net::SocketAddr>,
+    /// Peers the node stays connected to.
+    #[ts(as = "Vec<String>")]
+    pub connect: Vec<ConnectAddress>,
+    /// Addresses other nodes are told to reach us on.
+    #[ts(as = "Vec<String>")]
+    pub external_addresses: Vec<Address>,
+}
+
+/// Changes to the profile configuration. Settings left out are kept as they
+/// are.
+#[derive(Debug, Default, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "config/")]
+pub struct ConfigEdit {
+    #[ts(optional)]
+    pub alias: Option<String>,
+    #[ts(
+        type = "{ default: 'allow', scope: 'followed' | 'all' } | { default: 'block' }",
+        optional
+    )]
+    pub seeding_policy: Option<DefaultSeedingPolicy>,
+    #[ts(optional)]
+    pub public_explorer: Option<String>,
+    #[ts(optional)]
+    pub preferred_seeds: Option<Vec<String>>,
+    #[ts(optional)]
+    pub listen: Option<Vec<String>>,
+    #[ts(optional)]
+    pub connect: Option<Vec<String>>,
+    #[ts(optional)]
+    pub external_addresses: Option<Vec<String>>,
+}
+
+impl ConfigEdit {
+    /// Write the changes into the raw `config.json` contents. The values are
+    /// only checked as far as the alias goes; the whole configuration is
+    /// validated once it's read back.
+    pub fn apply(self, raw: &mut serde_json::Value) -> Result<(), Error> {
+        if let Some(alias) = self.alias {
+            set(raw, &["node", "alias"], Alias::from_str(&alias)?)?;
+        }
+        if let Some(policy) = self.seeding_policy {
+            set(raw, &["node", "seedingPolicy"], policy)?;
+        }
+        if let Some(explorer) = self.public_explorer {
+            set(raw, &["publicExplorer"], explorer)?;
+        }
+        if let Some(seeds) = self.preferred_seeds {
+            set(raw, &["preferredSeeds"], seeds)?;
+        }
+        if let Some(listen) = self.listen {
+            set(raw, &["node", "listen"], listen)?;
+        }
+        if let Some(connect) = self.connect {
+            set(raw, &["node", "connect"], connect)?;
+        }
+        if let Some(addresses) = self.external_addresses {
+            set(raw, &["node", "externalAddresses"], addresses)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Set the value at `path`, creating the objects on the way.
+fn set(raw: &mut serde_json::Value, path: &[&str], value: impl Serialize) -> Result<(), Error> {
+    let mut target = raw;
+    for key in path {
+        if !target.is_object() {
+            *target = serde_json::Value::Object(Default::default());
+        }
+        target = &mut target[*key];
+    }
+    *target = serde_json::to_value(value)?;
+
+    Ok(())
+}
+
+/// The outcome of editing the configuration.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "config/")]
+pub struct ConfigUpdate {
+    pub config: Config,
+    /// Changed settings the node only picks up when it restarts.
+    pub restart: Vec<NodeSetting>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "config/")]
+pub enum NodeSetting {
+    Alias,
+    SeedingPolicy,
+    Listen,
+    Connect,
+    ExternalAddresses,
+}
+
+impl NodeSetting {
+    /// The settings that differ between `old` and `new`.
+    pub fn changed(
+        old: &radicle::node::config::Config,
+        new: &radicle::node::config::Config,
+    ) -> Vec<Self> {
+        [
+            (Self::Alias, old.alias != new.alias),
+            (
+                Self::SeedingPolicy,
+                old.seeding_policy != new.seeding_policy,
+            ),
+            (Self::Listen, old.listen != new.listen),
+            (Self::Connect, old.connect != new.connect),
+            (
+                Self::ExternalAddresses,
+                old.external_addresses != new.external_addresses,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(setting, changed)| changed.then_some(setting))
+        .collect()
+    }
 }
 
 pub struct Version {
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use radicle::identity::RepoId;
+use radicle::node::NodeId;
+
+/// The outcome of checking local storage and the COB cache.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "diagnostics/")]
+pub struct Report {
+    pub cache: CacheHealth,
+    /// How many repositories were checked.
+    pub checked: usize,
+    /// The repositories with at least one problem.
+    pub repos: Vec<RepoHealth>,
+    /// Actions that should make the cache usable again, for every repository
+    /// at once.
+    pub repairs: Vec<Repair>,
+}
+
+impl Report {
+    pub fn new(cache: CacheHealth, checked: usize, repos: Vec<RepoHealth>) -> Self {
+        let mut repairs = Vec::new();
+        if matches!(cache, CacheHealth::Unusable { .. }) {
+            repairs.push(Repair::RebuildCaches);
+        }
+
+        Self {
+            cache,
+            checked,
+            repos,
+            repairs,
+        }
+    }
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "status", rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "diagnostics/")]
+pub enum CacheHealth {
+    Ok,
+    /// The COB cache can't be used as is, e.g. it needs a migration.
+    #[serde(rename_all = "camelCase")]
+    Unusable {
+        reason: String,
+    },
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "diagnostics/")]
+pub struct RepoHealth {
+    #[ts(as = "String")]
+    pub rid: RepoId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub name: Option<String>,
+    pub problems: Vec<Problem>,
+    /// Actions that should fix the problems, in the order to try them.
+    pub repairs: Vec<Repair>,
+}
+
+impl RepoHealth {
+    pub fn new(rid: RepoId, name: Option<String>, problems: Vec<Problem>) -> Self {
+        let mut repairs = Vec::new();
+        if problems.iter().any(Problem::needs_refetch) {
+            repairs.push(Repair::Refetch);
+        }
+        if problems.iter().any(Problem::needs_rebuild) {
+            repairs.push(Repair::RebuildCaches);
+        }
+
+        Self {
+            rid,
+            name,
+            problems,
+            repairs,
+        }
+    }
+}
+
+#[derive(Serialize, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "diagnostics/")]
+pub enum Problem {
+    /// The repository or its identity document can't be read.
+    #[serde(rename_all = "camelCase")]
+    Unreadable { reason: String },
+    /// The signed refs of a remote don't verify.
+    #[serde(rename_all = "camelCase")]
+    InvalidSignedRefs {
+        #[ts(as = "String")]
+        remote: NodeId,
+        reason: String,
+    },
+    /// Our signed refs are at an older feature level and wait for the node
+    /// to migrate them.
+    #[serde(rename_all = "camelCase")]
+    NeedsMigration {
+        #[ts(as = "String")]
+        remote: NodeId,
+    },
+    /// A COB ref points to an object that isn't in storage.
+    #[serde(rename_all = "camelCase")]
+    DanglingCobRef { reference: String },
+    /// The COB cache doesn't hold the same objects as storage.
+    #[serde(rename_all = "camelCase")]
+    CacheMismatch {
+        cob: CobKind,
+        /// Objects in storage that aren't cached.
+        missing: usize,
+        /// Cached objects that aren't in storage.
+        stale: usize,
+    },
+}
+
+impl Problem {
+    fn needs_refetch(&self) -> bool {
+        matches!(
+            self,
+            Self::Unreadable { .. } | Self::InvalidSignedRefs { .. } | Self::DanglingCobRef { .. }
+        )
+    }
+
+    fn needs_rebuild(&self) -> bool {
+        matches!(self, Self::CacheMismatch { .. })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "diagnostics/")]
+pub enum CobKind {
+    Issues,
+    Patches,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "diagnostics/")]
+pub enum Repair {
+    /// Rewrite the issue and patch caches from storage.
+    RebuildCaches,
+    /// Fetch the repository again from the seeds that have it.
+    Refetch,
+}
@@ -1,3 +1,4 @@
 pub mod inbox;
 pub mod issue;
 pub mod patch;
+pub mod search;
@@ -22,6 +22,51 @@ pub enum SetStatusNotifications {
     All,
 }
 
+/// Where a notification stands, as tracked by the app.
+///
+/// Notifications start out unread. A snoozed notification turns unread again
+/// once its snooze time has passed, and a done one is hidden until the node
+/// records new activity on the same object.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "cob/inbox/")]
+pub enum NotificationState {
+    #[default]
+    Unread,
+    Read,
+    Snoozed,
+    Done,
+}
+
+impl NotificationState {
+    /// The states listed when none are asked for.
+    pub const VISIBLE: [Self; 2] = [Self::Unread, Self::Read];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unread => "unread",
+            Self::Read => "read",
+            Self::Snoozed => "snoozed",
+            Self::Done => "done",
+        }
+    }
+}
+
+impl std::str::FromStr for NotificationState {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unread" => Ok(Self::Unread),
+            "read" => Ok(Self::Read),
+            "snoozed" => Ok(Self::Snoozed),
+            "done" => Ok(Self::Done),
+            _ => Err(anyhow::anyhow!("unknown notification state `{s}`")),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename = "camelCase")]
 pub struct NotificationRow {
@@ -32,6 +77,9 @@ pub struct NotificationRow {
     pub old: Option<git::Oid>,
     pub new: Option<git::Oid>,
     pub repo: Option<identity::RepoId>,
+    /// Not stored by the node, filled in from the app's own records.
+    #[serde(default)]
+    pub state: NotificationState,
 }
 
 pub type RepoGroup = Vec<(git::fmt::Qualified<'static>, Vec<NotificationRow>)>;
@@ -44,11 +92,14 @@ pub struct CountsByRepoParams {
     pub repo: identity::RepoId,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, Default, serde::Deserialize)]
 pub struct RepoGroupParams {
     pub repos: Option<Vec<identity::RepoId>>,
     pub take: Option<usize>,
     pub all: Option<bool>,
+    /// Only list notifications in one of these states, unread and read ones
+    /// if not set.
+    pub states: Option<Vec<NotificationState>>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -73,6 +124,18 @@ pub enum ListNotificationsError {
     // to be extended as new error scenarios are introduced
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum MarkNotificationsError {
+    #[error("a notification can only be snoozed until a later time")]
+    SnoozeTime,
+
+    #[error(transparent)]
+    ListNotifications(#[from] ListNotificationsError),
+
+    #[error(transparent)]
+    Sqlite(#[from] sqlite::Error),
+}
+
 pub fn actions<A>(
     typename: cob::TypeName,
     oid: cob::ObjectId,
@@ -137,6 +200,7 @@ pub struct Issue {
     pub timestamp: radicle_localtime::LocalTime,
     pub status: cobs::issue::State,
     pub actions: Vec<ActionWithAuthor<cobs::issue::Action>>,
+    pub state: NotificationState,
     #[ts(as = "String")]
     pub repo_id: Option<identity::RepoId>,
 }
@@ -168,6 +232,7 @@ pub struct Patch {
     pub title: String,
     pub status: models::patch::State,
     pub actions: Vec<ActionWithAuthor<models::patch::Action>>,
+    pub state: NotificationState,
     #[ts(as = "String")]
     pub repo_id: Option<identity::RepoId>,
 }
@@ -1,7 +1,8 @@
 use radicle::identity;
 
 use crate::domain::inbox::models::notification::{
-    CountByRepo, ListNotificationsError, RepoGroup, RepoGroupParams,
+    CountByRepo, ListNotificationsError, MarkNotificationsError, NotificationState, RepoGroup,
+    RepoGroupParams, SetStatusNotifications,
 };
 use crate::domain::inbox::traits::{InboxService, InboxStorage};
 
@@ -45,4 +46,13 @@ where
     ) -> Result<Vec<(identity::RepoId, RepoGroup)>, ListNotificationsError> {
         self.inbox.repo_group(params)
     }
+
+    fn mark(
+        &self,
+        notifications: SetStatusNotifications,
+        state: NotificationState,
+        until: Option<u64>,
+    ) -> Result<(), MarkNotificationsError> {
+        self.inbox.mark(notifications, state, until)
+    }
 }
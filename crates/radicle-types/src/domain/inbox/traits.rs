@@ -1,7 +1,8 @@
 use radicle::identity;
 
 use crate::domain::inbox::models::notification::{
-    CountByRepo, ListNotificationsError, RepoGroupParams,
+    CountByRepo, ListNotificationsError, MarkNotificationsError, NotificationState,
+    RepoGroupParams, SetStatusNotifications,
 };
 
 use super::models::notification::RepoGroup;
@@ -20,10 +21,17 @@ pub trait InboxStorage {
         &self,
         params: RepoGroupParams,
     ) -> Result<Vec<(identity::RepoId, RepoGroup)>, ListNotificationsError>;
+
+    fn mark(
+        &self,
+        notifications: SetStatusNotifications,
+        state: NotificationState,
+        until: Option<u64>,
+    ) -> Result<(), MarkNotificationsError>;
 }
 
 pub trait InboxService {
-    /// Get the total notification count by repos.
+    /// Get the unread notification count by repos.
     fn counts_by_repo(
         &self,
     ) -> Result<
@@ -31,11 +39,20 @@ pub trait InboxService {
         ListNotificationsError,
     >;
 
-    /// Get the total notification count.
+    /// Get the unread notification count.
     fn notification_count(&self) -> Result<usize, ListNotificationsError>;
 
     fn repo_group(
         &self,
         params: RepoGroupParams,
     ) -> Result<Vec<(identity::RepoId, RepoGroup)>, ListNotificationsError>;
+
+    /// Set the state of notifications, without removing them from the node.
+    /// Snoozing needs a time, in milliseconds since the epoch, to snooze until.
+    fn mark(
+        &self,
+        notifications: SetStatusNotifications,
+        state: NotificationState,
+        until: Option<u64>,
+    ) -> Result<(), MarkNotificationsError>;
 }
@@ -1,4 +1,6 @@
+use serde::Serialize;
 use thiserror::Error;
+use ts_rs::TS;
 
 /// Issue state filter understood by the COB cache queries.
 #[derive(Debug, Clone, Copy)]
@@ -25,3 +27,32 @@ pub enum ListIssuesError {
     Unknown(#[from] anyhow::Error),
     // to be extended as new error scenarios are introduced
 }
+
+#[derive(Debug, Default, TS, Serialize)]
+#[ts(export)]
+#[ts(export_to = "cob/issue/")]
+#[serde(rename_all = "camelCase")]
+pub struct IssueCounts {
+    pub(crate) open: usize,
+    pub(crate) closed: usize,
+}
+
+impl IssueCounts {
+    pub fn new(open: usize, closed: usize) -> Self {
+        Self { open, closed }
+    }
+
+    pub fn total(&self) -> usize {
+        self.open + self.closed
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CountsError {
+    #[error(transparent)]
+    Sqlite(#[from] sqlite::Error),
+
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+    // to be extended as new error scenarios are introduced
+}
@@ -1,6 +1,7 @@
 use radicle::identity;
 use radicle::issue::{Issue, IssueId};
 
+use crate::cobs;
 use crate::domain::issue::models;
 use crate::domain::issue::traits::{IssueService, IssueStorage};
 
@@ -39,4 +40,30 @@ where
     ) -> Result<impl Iterator<Item = (IssueId, Issue)>, models::issue::ListIssuesError> {
         self.issues.list_by_status(rid, status)
     }
+
+    fn list_filtered(
+        &self,
+        rid: identity::RepoId,
+        status: Option<models::issue::Status>,
+        filter: &cobs::query::ListFilter,
+        after: Option<&cobs::Cursor>,
+    ) -> Result<impl Iterator<Item = (IssueId, Issue, cobs::Cursor)>, models::issue::ListIssuesError>
+    {
+        self.issues.list_filtered(rid, status, filter, after)
+    }
+
+    fn counts(
+        &self,
+        rid: identity::RepoId,
+    ) -> Result<models::issue::IssueCounts, models::issue::CountsError> {
+        self.issues.counts(rid)
+    }
+
+    fn facets(
+        &self,
+        rid: identity::RepoId,
+        status: Option<models::issue::Status>,
+    ) -> Result<cobs::FacetCounts, models::issue::CountsError> {
+        self.issues.facets(rid, status)
+    }
 }
@@ -17,6 +17,30 @@ pub trait IssueStorage {
         rid: identity::RepoId,
         status: Status,
     ) -> Result<impl Iterator<Item = (IssueId, Issue)>, models::issue::ListIssuesError>;
+
+    /// Issues for `rid` with the given status, or any status, narrowed and
+    /// ordered by `filter`, each with its position in that order. With `after`
+    /// set, the list starts right after that position.
+    fn list_filtered(
+        &self,
+        rid: identity::RepoId,
+        status: Option<Status>,
+        filter: &cobs::query::ListFilter,
+        after: Option<&cobs::Cursor>,
+    ) -> Result<impl Iterator<Item = (IssueId, Issue, cobs::Cursor)>, models::issue::ListIssuesError>;
+
+    fn counts(
+        &self,
+        rid: identity::RepoId,
+    ) -> Result<models::issue::IssueCounts, models::issue::CountsError>;
+
+    /// Label and assignee counts over the issues with the given status, or
+    /// all issues.
+    fn facets(
+        &self,
+        rid: identity::RepoId,
+        status: Option<Status>,
+    ) -> Result<cobs::FacetCounts, models::issue::CountsError>;
 }
 
 pub trait IssueService {
@@ -31,46 +55,103 @@ pub trait IssueService {
         status: Status,
     ) -> Result<impl Iterator<Item = (IssueId, Issue)>, models::issue::ListIssuesError>;
 
+    /// Issues for `rid` with the given status, or any status, narrowed and
+    /// ordered by `filter`, each with its position in that order. With `after`
+    /// set, the list starts right after that position.
+    fn list_filtered(
+        &self,
+        rid: identity::RepoId,
+        status: Option<Status>,
+        filter: &cobs::query::ListFilter,
+        after: Option<&cobs::Cursor>,
+    ) -> Result<impl Iterator<Item = (IssueId, Issue, cobs::Cursor)>, models::issue::ListIssuesError>;
+
+    fn counts(
+        &self,
+        rid: identity::RepoId,
+    ) -> Result<models::issue::IssueCounts, models::issue::CountsError>;
+
+    /// Label and assignee counts over the issues with the given status, or
+    /// all issues.
+    fn facets(
+        &self,
+        rid: identity::RepoId,
+        status: Option<Status>,
+    ) -> Result<cobs::FacetCounts, models::issue::CountsError>;
+
+    /// State counts for the sidebar, with facets over the issues in
+    /// `status` so filter chips can show how many issues they'd match.
+    fn faceted_counts(
+        &self,
+        rid: identity::RepoId,
+        status: cobs::query::IssueStatus,
+        aliases: &impl AliasStore,
+    ) -> Result<cobs::FacetedCounts<models::issue::IssueCounts>, models::issue::CountsError> {
+        let status = match status {
+            cobs::query::IssueStatus::All => None,
+            cobs::query::IssueStatus::Open => Some(Status::Open),
+            cobs::query::IssueStatus::Closed => Some(Status::Closed),
+        };
+
+        Ok(cobs::FacetedCounts {
+            counts: self.counts(rid)?,
+            facets: self.facets(rid, status)?.resolve(aliases),
+        })
+    }
+
     /// One page of issue summaries, shared by all drivers so pagination
     /// behaves identically everywhere. Rows are consumed lazily: with `take`
     /// set, issues beyond the requested page are never deserialized. Without
-    /// `take` the full list is returned and `skip` is ignored.
+    /// `take` the rest of the list is returned and `skip` is ignored. A page
+    /// starts after `after` when set, else `skip` rows in.
+    #[allow(clippy::too_many_arguments)]
     fn list_paginated(
         &self,
         rid: identity::RepoId,
         status: cobs::query::IssueStatus,
+        filter: &cobs::query::ListFilter,
         skip: Option<usize>,
+        after: Option<cobs::Cursor>,
         take: Option<usize>,
         aliases: &impl AliasStore,
     ) -> Result<cobs::PaginatedQuery<Vec<cobs::issue::Issue>>, models::issue::ListIssuesError> {
-        let issues: Box<dyn Iterator<Item = (IssueId, Issue)> + '_> = match status {
-            cobs::query::IssueStatus::All => Box::new(self.list(rid)?),
-            cobs::query::IssueStatus::Open => Box::new(self.list_by_status(rid, Status::Open)?),
-            cobs::query::IssueStatus::Closed => Box::new(self.list_by_status(rid, Status::Closed)?),
+        let status = match status {
+            cobs::query::IssueStatus::All => None,
+            cobs::query::IssueStatus::Open => Some(Status::Open),
+            cobs::query::IssueStatus::Closed => Some(Status::Closed),
+        };
+        let issues = self.list_filtered(rid, status, filter, after.as_ref())?;
+        let summary = |(id, issue, _): (IssueId, Issue, cobs::Cursor)| {
+            cobs::issue::Issue::summary(&id, &issue, aliases)
         };
-        let summary =
-            |(id, issue): (IssueId, Issue)| cobs::issue::Issue::summary(&id, &issue, aliases);
 
         match take {
             None => Ok(cobs::PaginatedQuery {
                 cursor: 0,
                 more: false,
                 content: issues.map(summary).collect::<Vec<_>>(),
+                next: None,
             }),
             Some(take) => {
-                let cursor = skip.unwrap_or(0);
-                let mut content = issues
-                    .skip(cursor)
-                    .take(take + 1)
-                    .map(summary)
-                    .collect::<Vec<_>>();
-                let more = content.len() > take;
-                content.truncate(take);
+                let cursor = if after.is_some() {
+                    0
+                } else {
+                    skip.unwrap_or(0)
+                };
+                let mut page = issues.skip(cursor).take(take + 1).collect::<Vec<_>>();
+                let more = page.len() > take;
+                page.truncate(take);
+                let next = if more {
+                    page.last().map(|(_, _, position)| position.clone())
+                } else {
+                    None
+                };
 
                 Ok(cobs::PaginatedQuery {
                     cursor,
                     more,
-                    content,
+                    content: page.into_iter().map(summary).collect(),
+                    next,
                 })
             }
         }
@@ -769,6 +769,61 @@ pub struct CreateReviewComment {
     pub location: Option<cobs::thread::CodeLocation>,
 }
 
+/// A patch to open. Its head is `sha` when given, otherwise `branch` resolved
+/// under `peer` the way the source browser does.
+#[derive(Debug, TS, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "cob/patch/")]
+pub struct NewPatch {
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    #[ts(as = "Option<String>", optional)]
+    pub sha: Option<git::Oid>,
+    #[serde(default)]
+    #[ts(as = "Option<String>", optional)]
+    pub peer: Option<radicle::node::NodeId>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    #[ts(as = "Option<String>", optional)]
+    pub target: patch::MergeTarget,
+    #[serde(default)]
+    #[ts(as = "Option<Vec<String>>", optional)]
+    pub labels: Vec<cob::Label>,
+    #[serde(default)]
+    #[ts(as = "Option<bool>", optional)]
+    pub draft: bool,
+    #[serde(default)]
+    #[ts(as = "Option<_>", optional)]
+    pub embeds: Vec<cobs::thread::Embed>,
+}
+
+/// A new revision for an existing patch. The head is `sha` when given,
+/// otherwise `branch` resolved under `peer` the way the source browser does.
+#[derive(Debug, TS, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "cob/patch/")]
+pub struct CreateRevisionArgs {
+    #[ts(as = "String")]
+    pub rid: RepoId,
+    #[ts(as = "String")]
+    pub patch_id: patch::PatchId,
+    #[serde(default)]
+    #[ts(as = "Option<String>", optional)]
+    pub sha: Option<git::Oid>,
+    #[serde(default)]
+    #[ts(as = "Option<String>", optional)]
+    pub peer: Option<radicle::node::NodeId>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub branch: Option<String>,
+    pub description: String,
+}
+
 #[derive(Debug, Default, TS, Serialize)]
 #[ts(export)]
 #[ts(export_to = "cob/patch/")]
@@ -3,6 +3,7 @@ use radicle::patch;
 use radicle::patch::Patch;
 use radicle::patch::PatchId;
 
+use crate::cobs;
 use crate::domain::patch::traits::{PatchService, PatchStorage};
 
 use super::models::patch::PatchCounts;
@@ -45,10 +46,31 @@ where
         self.patches.list_by_status(rid, status)
     }
 
+    fn list_filtered(
+        &self,
+        rid: identity::RepoId,
+        status: Option<patch::Status>,
+        filter: &cobs::query::ListFilter,
+        after: Option<&cobs::Cursor>,
+    ) -> Result<
+        impl Iterator<Item = (PatchId, Patch, cobs::Cursor)>,
+        super::models::patch::ListPatchesError,
+    > {
+        self.patches.list_filtered(rid, status, filter, after)
+    }
+
     fn counts(
         &self,
         rid: identity::RepoId,
     ) -> Result<PatchCounts, super::models::patch::CountsError> {
         self.patches.counts(rid)
     }
+
+    fn facets(
+        &self,
+        rid: identity::RepoId,
+        status: Option<patch::Status>,
+    ) -> Result<cobs::FacetCounts, super::models::patch::CountsError> {
+        self.patches.facets(rid, status)
+    }
 }
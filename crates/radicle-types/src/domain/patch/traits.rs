@@ -19,10 +19,29 @@ pub trait PatchStorage {
         status: patch::Status,
     ) -> Result<impl Iterator<Item = (PatchId, Patch)>, models::patch::ListPatchesError>;
 
+    /// Patches for `rid` with the given status, or any status, narrowed and
+    /// ordered by `filter`, each with its position in that order. With `after`
+    /// set, the list starts right after that position.
+    fn list_filtered(
+        &self,
+        rid: identity::RepoId,
+        status: Option<patch::Status>,
+        filter: &cobs::query::ListFilter,
+        after: Option<&cobs::Cursor>,
+    ) -> Result<impl Iterator<Item = (PatchId, Patch, cobs::Cursor)>, models::patch::ListPatchesError>;
+
     fn counts(
         &self,
         rid: identity::RepoId,
     ) -> Result<models::patch::PatchCounts, models::patch::CountsError>;
+
+    /// Label and assignee counts over the patches with the given status, or
+    /// all patches.
+    fn facets(
+        &self,
+        rid: identity::RepoId,
+        status: Option<patch::Status>,
+    ) -> Result<cobs::FacetCounts, models::patch::CountsError>;
 }
 
 pub trait PatchService {
@@ -37,30 +56,64 @@ pub trait PatchService {
         status: patch::Status,
     ) -> Result<impl Iterator<Item = (PatchId, Patch)>, models::patch::ListPatchesError>;
 
+    /// Patches for `rid` with the given status, or any status, narrowed and
+    /// ordered by `filter`, each with its position in that order. With `after`
+    /// set, the list starts right after that position.
+    fn list_filtered(
+        &self,
+        rid: identity::RepoId,
+        status: Option<patch::Status>,
+        filter: &cobs::query::ListFilter,
+        after: Option<&cobs::Cursor>,
+    ) -> Result<impl Iterator<Item = (PatchId, Patch, cobs::Cursor)>, models::patch::ListPatchesError>;
+
     fn counts(
         &self,
         rid: identity::RepoId,
     ) -> Result<models::patch::PatchCounts, models::patch::CountsError>;
 
+    /// Label and assignee counts over the patches with the given status, or
+    /// all patches.
+    fn facets(
+        &self,
+        rid: identity::RepoId,
+        status: Option<patch::Status>,
+    ) -> Result<cobs::FacetCounts, models::patch::CountsError>;
+
+    /// State counts for the sidebar, with facets over the patches in
+    /// `status` so filter chips can show how many patches they'd match.
+    fn faceted_counts(
+        &self,
+        rid: identity::RepoId,
+        status: Option<cobs::query::PatchStatus>,
+        aliases: &impl AliasStore,
+    ) -> Result<cobs::FacetedCounts<models::patch::PatchCounts>, models::patch::CountsError> {
+        Ok(cobs::FacetedCounts {
+            counts: self.counts(rid)?,
+            facets: self.facets(rid, status.map(Into::into))?.resolve(aliases),
+        })
+    }
+
     /// One page of patch summaries, shared by all drivers so pagination
     /// behaves identically everywhere. Rows are consumed lazily: with `take`
     /// set, patches beyond the requested page are never deserialized.
-    /// Without `take` the full list is returned and `skip` is ignored.
+    /// Without `take` the rest of the list is returned and `skip` is ignored.
+    /// A page starts after `after` when set, else `skip` rows in.
+    #[allow(clippy::too_many_arguments)]
     fn list_paginated(
         &self,
         rid: identity::RepoId,
         status: Option<cobs::query::PatchStatus>,
+        filter: &cobs::query::ListFilter,
         skip: Option<usize>,
+        after: Option<cobs::Cursor>,
         take: Option<usize>,
         delegates: &[identity::Did],
         aliases: &impl AliasStore,
     ) -> Result<cobs::PaginatedQuery<Vec<models::patch::Patch>>, models::patch::ListPatchesError>
     {
-        let patches: Box<dyn Iterator<Item = (PatchId, Patch)> + '_> = match status {
-            None => Box::new(self.list(rid)?),
-            Some(status) => Box::new(self.list_by_status(rid, status.into())?),
-        };
-        let summary = |(id, patch): (PatchId, Patch)| {
+        let patches = self.list_filtered(rid, status.map(Into::into), filter, after.as_ref())?;
+        let summary = |(id, patch, _): (PatchId, Patch, cobs::Cursor)| {
             models::patch::Patch::new(id, &patch, delegates, aliases)
         };
 
@@ -69,21 +122,28 @@ pub trait PatchService {
                 cursor: 0,
                 more: false,
                 content: patches.map(summary).collect::<Vec<_>>(),
+                next: None,
             }),
             Some(take) => {
-                let cursor = skip.unwrap_or(0);
-                let mut content = patches
-                    .skip(cursor)
-                    .take(take + 1)
-                    .map(summary)
-                    .collect::<Vec<_>>();
-                let more = content.len() > take;
-                content.truncate(take);
+                let cursor = if after.is_some() {
+                    0
+                } else {
+                    skip.unwrap_or(0)
+                };
+                let mut page = patches.skip(cursor).take(take + 1).collect::<Vec<_>>();
+                let more = page.len() > take;
+                page.truncate(take);
+                let next = if more {
+                    page.last().map(|(_, _, position)| position.clone())
+                } else {
+                    None
+                };
 
                 Ok(cobs::PaginatedQuery {
                     cursor,
                     more,
-                    content,
+                    content: page.into_iter().map(summary).collect(),
+                    next,
                 })
             }
         }
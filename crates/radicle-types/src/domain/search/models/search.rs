@@ -0,0 +1,96 @@
+use radicle::cob;
+use radicle::identity::{Did, RepoId};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+
+use crate::cobs;
+
+/// Upper bound on the hits returned for one query.
+pub const MAX_HITS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "search/")]
+pub enum Kind {
+    Issue,
+    Patch,
+}
+
+impl Kind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Issue => "issue",
+            Self::Patch => "patch",
+        }
+    }
+}
+
+impl std::str::FromStr for Kind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "issue" => Ok(Self::Issue),
+            "patch" => Ok(Self::Patch),
+            _ => Err(anyhow::anyhow!("unknown document kind `{s}`")),
+        }
+    }
+}
+
+/// A full-text query with optional filters; every filter that is set must
+/// match.
+#[derive(Debug, Default, Clone)]
+pub struct SearchQuery {
+    pub query: String,
+    pub rids: Option<Vec<RepoId>>,
+    pub kinds: Option<Vec<Kind>>,
+    /// Author of the matching text, e.g. the comment rather than its issue.
+    pub author: Option<Did>,
+    pub label: Option<cob::Label>,
+}
+
+/// An indexed piece of text matching a query, as stored.
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub kind: Kind,
+    pub rid: RepoId,
+    pub id: cob::ObjectId,
+    pub entry: Option<cob::EntryId>,
+    pub author: Did,
+    pub title: String,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+/// A ranked search result. `entry` points at the comment, revision or review
+/// the match is in, and is absent when the title matched. Matched terms in
+/// `snippet` are wrapped in `<mark>` tags; the rest is the text as written.
+#[derive(Debug, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "search/")]
+pub struct SearchHit {
+    pub kind: Kind,
+    #[ts(as = "String")]
+    pub rid: RepoId,
+    #[ts(as = "String")]
+    pub id: cob::ObjectId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub entry: Option<cob::EntryId>,
+    pub author: cobs::Author,
+    pub title: String,
+    pub snippet: String,
+}
+
+#[derive(Debug, Error)]
+pub enum SearchError {
+    #[error(transparent)]
+    Sqlite(#[from] sqlite::Error),
+
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+    // to be extended as new error scenarios are introduced
+}
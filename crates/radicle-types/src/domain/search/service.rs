@@ -0,0 +1,32 @@
+use crate::domain::search::models;
+use crate::domain::search::traits::{SearchService, SearchStorage};
+
+#[derive(Debug, Clone)]
+pub struct Service<I>
+where
+    I: SearchStorage,
+{
+    index: I,
+}
+
+impl<I> Service<I>
+where
+    I: SearchStorage,
+{
+    pub fn new(index: I) -> Self {
+        Self { index }
+    }
+}
+
+impl<I> SearchService for Service<I>
+where
+    I: SearchStorage,
+{
+    fn search(
+        &self,
+        query: &models::search::SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<models::search::Match>, models::search::SearchError> {
+        self.index.search(query, limit)
+    }
+}
@@ -0,0 +1,43 @@
+use radicle::node::AliasStore;
+
+use crate::cobs;
+use crate::domain::search::models;
+
+pub trait SearchStorage {
+    /// Matches for `query`, best first, at most `limit` of them.
+    fn search(
+        &self,
+        query: &models::search::SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<models::search::Match>, models::search::SearchError>;
+}
+
+pub trait SearchService {
+    fn search(
+        &self,
+        query: &models::search::SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<models::search::Match>, models::search::SearchError>;
+
+    /// Matches for `query` ready for display, with their authors' aliases.
+    fn search_hits(
+        &self,
+        query: &models::search::SearchQuery,
+        limit: usize,
+        aliases: &impl AliasStore,
+    ) -> Result<Vec<models::search::SearchHit>, models::search::SearchError> {
+        Ok(self
+            .search(query, limit)?
+            .into_iter()
+            .map(|m| models::search::SearchHit {
+                kind: m.kind,
+                rid: m.rid,
+                id: m.id,
+                entry: m.entry,
+                author: cobs::Author::new(&m.author, aliases),
+                title: m.title,
+                snippet: m.snippet,
+            })
+            .collect())
+    }
+}
@@ -23,6 +23,10 @@ pub enum Error {
     #[error("ssh agent not running")]
     AgentNotRunning,
 
+    /// The node can't get at an encrypted secret key.
+    #[error("the secret key is encrypted and not in the ssh-agent, run `rad auth` to add it")]
+    KeyNotInAgent,
+
     /// File size too big
     #[error("file size too large: {0}")]
     FileTooLarge(usize),
@@ -39,10 +43,27 @@ pub enum Error {
     #[error("you have already reviewed this revision")]
     ReviewExists,
 
+    /// A patch revision that can't be merged into the default branch.
+    #[error("revision cannot be merged: {0}")]
+    NotMergeable(String),
+
+    /// A new revision pointing at the commit the patch is already at.
+    #[error("patch is already at this commit")]
+    PatchUpToDate,
+
     /// Init Error error.
     #[error(transparent)]
     InitError(#[from] radicle::rad::InitError),
 
+    /// A configuration that can't be loaded, e.g. because of a malformed
+    /// address.
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    /// Removing the profile in use.
+    #[error("the active profile can't be removed")]
+    ActiveProfile,
+
     /// Alias error.
     #[error(transparent)]
     AliasError(#[from] radicle::node::AliasError),
@@ -63,12 +84,30 @@ pub enum Error {
     #[error(transparent)]
     ProjectError(#[from] radicle::identity::project::ProjectError),
 
+    /// Identity document error.
+    #[error(transparent)]
+    Doc(#[from] radicle::identity::doc::DocError),
+
+    /// Identity document payload error.
+    #[error(transparent)]
+    Payload(#[from] radicle::identity::doc::PayloadError),
+
+    /// Identity COB error.
+    #[error(transparent)]
+    Identity(#[from] radicle::cob::identity::Error),
+
     /// List notification error.
     #[error(transparent)]
     ListNotificationsError(
         #[from] crate::domain::inbox::models::notification::ListNotificationsError,
     ),
 
+    /// Mark notifications error.
+    #[error(transparent)]
+    MarkNotificationsError(
+        #[from] crate::domain::inbox::models::notification::MarkNotificationsError,
+    ),
+
     /// CobStore error.
     #[error(transparent)]
     ListPatchesError(#[from] crate::domain::patch::models::patch::ListPatchesError),
@@ -76,9 +115,15 @@ pub enum Error {
     #[error(transparent)]
     PatchCountsError(#[from] crate::domain::patch::models::patch::CountsError),
 
+    #[error(transparent)]
+    IssueCountsError(#[from] crate::domain::issue::models::issue::CountsError),
+
     #[error(transparent)]
     ListIssuesError(#[from] crate::domain::issue::models::issue::ListIssuesError),
 
+    #[error(transparent)]
+    SearchError(#[from] crate::domain::search::models::search::SearchError),
+
     /// CobStore error.
     #[error(transparent)]
     CobStore(#[from] radicle::cob::store::Error),
@@ -119,6 +164,14 @@ pub enum Error {
     #[error(transparent)]
     Database(#[from] radicle::node::db::Error),
 
+    /// Routing table error.
+    #[error(transparent)]
+    Routing(#[from] radicle::node::routing::Error),
+
+    /// Seed database error.
+    #[error(transparent)]
+    Seed(#[from] radicle::node::seed::Error),
+
     /// Repository error.
     #[error(transparent)]
     SurfFsError(#[from] radicle_surf::fs::error::Directory),
@@ -159,6 +212,10 @@ pub enum Error {
     #[error(transparent)]
     Git2(#[from] radicle::git::raw::Error),
 
+    /// Reference name error.
+    #[error(transparent)]
+    RefFormat(#[from] radicle::git::fmt::Error),
+
     /// Cob issue cache error.
     #[error(transparent)]
     CacheIssue(#[from] radicle::cob::issue::cache::Error),
@@ -212,8 +269,13 @@ impl Error {
             Error::AliasError(radicle::node::AliasError::InvalidCharacter) => {
                 "AliasError.InvalidAlias"
             }
+            Error::InvalidConfig(_) => "ConfigError.Invalid",
+            Error::ActiveProfile => "ProfileError.Active",
+            Error::KeyNotInAgent => "NodeError.KeyNotInAgent",
             Error::FileTooLarge(_) => "PayloadError.TooLarge",
             Error::ReviewExists => "PatchError.ReviewExists",
+            Error::NotMergeable(_) => "PatchError.NotMergeable",
+            Error::PatchUpToDate => "PatchError.UpToDate",
             _ => "UnknownError",
         }
     }
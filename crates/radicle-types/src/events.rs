@@ -0,0 +1,251 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use radicle::cob::{self, ObjectId};
+use radicle::identity::RepoId;
+use radicle::node::NodeId;
+use radicle::storage::RefUpdate;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::error::Error;
+use crate::outbound::sqlite::Sqlite;
+
+/// A change to local state, pushed to the frontend as a `radicle_event` so
+/// open views can refresh what changed instead of refetching blindly.
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
+#[serde(rename_all = "camelCase", tag = "event", content = "data")]
+#[ts(export)]
+#[ts(export_to = "events/")]
+pub enum Event {
+    PatchUpdated {
+        #[ts(as = "String")]
+        rid: RepoId,
+        #[ts(as = "String")]
+        id: ObjectId,
+    },
+    IssueUpdated {
+        #[ts(as = "String")]
+        rid: RepoId,
+        #[ts(as = "String")]
+        id: ObjectId,
+    },
+    RefsFetched {
+        #[ts(as = "String")]
+        rid: RepoId,
+        #[ts(as = "String")]
+        remote: NodeId,
+    },
+    /// The inbox grew; `count` is the new unread total.
+    NotificationCreated {
+        #[ts(type = "number")]
+        count: usize,
+    },
+}
+
+impl Event {
+    /// The event for a change to a patch or issue, `None` for other COB types.
+    fn cob(type_name: &str, rid: RepoId, id: ObjectId) -> Option<Self> {
+        if type_name == cob::patch::TYPENAME.to_string() {
+            Some(Self::PatchUpdated { rid, id })
+        } else if type_name == cob::issue::TYPENAME.to_string() {
+            Some(Self::IssueUpdated { rid, id })
+        } else {
+            None
+        }
+    }
+
+    /// The events a node fetch implies: the fetch itself, followed by one per
+    /// patch or issue whose COB ref it moved under any remote.
+    pub fn from_fetch(rid: RepoId, remote: NodeId, updated: &[RefUpdate]) -> Vec<Self> {
+        let cobs = updated
+            .iter()
+            .filter(|update| !matches!(update, RefUpdate::Skipped { .. }))
+            .filter_map(|update| cob_ref(update.name().as_str()))
+            .collect::<BTreeSet<_>>();
+
+        std::iter::once(Self::RefsFetched { rid, remote })
+            .chain(
+                cobs.into_iter()
+                    .filter_map(|(type_name, id)| Self::cob(type_name, rid, id)),
+            )
+            .collect()
+    }
+}
+
+/// Split a — possibly namespaced — `refs/cobs/<type>/<id>` ref into the COB's
+/// type name and id.
+fn cob_ref(name: &str) -> Option<(&str, ObjectId)> {
+    let name = match name.strip_prefix("refs/namespaces/") {
+        Some(rest) => rest.split_once('/')?.1,
+        None => name,
+    };
+    let (type_name, id) = name.strip_prefix("refs/cobs/")?.rsplit_once('/')?;
+
+    Some((type_name, id.parse().ok()?))
+}
+
+/// The patches and issues in the COB cache as of one read. Without a running
+/// node there is no event stream, but `rad` still writes through the cache, so
+/// comparing snapshots tells which objects changed in the meantime.
+pub struct Snapshot {
+    version: i64,
+    digests: BTreeMap<(String, RepoId, ObjectId), u64>,
+}
+
+impl Snapshot {
+    pub fn new(cobs: &Sqlite) -> Result<Self, Error> {
+        Ok(Self {
+            version: cobs.data_version()?,
+            digests: cobs.cob_digests()?,
+        })
+    }
+
+    /// Re-read the cache and return an event for every patch or issue that
+    /// was written or removed since the last read. While nothing was written
+    /// this costs a single pragma.
+    pub fn update(&mut self, cobs: &Sqlite) -> Result<Vec<Event>, Error> {
+        let version = cobs.data_version()?;
+        if version == self.version {
+            return Ok(Vec::new());
+        }
+        let digests = cobs.cob_digests()?;
+        let changed = digests
+            .iter()
+            .filter(|(key, digest)| self.digests.get(*key) != Some(*digest))
+            .map(|(key, _)| key)
+            .chain(
+                self.digests
+                    .keys()
+                    .filter(|key| !digests.contains_key(*key)),
+            )
+            .filter_map(|(type_name, rid, id)| Event::cob(type_name, *rid, *id))
+            .collect();
+
+        self.version = version;
+        self.digests = digests;
+
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::str::FromStr;
+
+    use radicle::cob::ObjectId;
+    use radicle::git;
+    use radicle::identity::RepoId;
+    use radicle::node::NodeId;
+    use radicle::storage::RefUpdate;
+    use sqlite as sql;
+
+    use super::{Event, Snapshot};
+    use crate::outbound::sqlite::Sqlite;
+
+    const PATCH: &str = "e7a8b5e4ab6c2a4e1b7b3a1ec9b8f5a6d2c1b0a9";
+    const ISSUE: &str = "0f3c8a2d7b6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a";
+
+    fn rid() -> RepoId {
+        "rad:z3gqcJUoA1n9HaHKufZs5FCSGazv5".parse().unwrap()
+    }
+
+    fn nid() -> NodeId {
+        "z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi"
+            .parse()
+            .unwrap()
+    }
+
+    fn name(refname: &str) -> git::fmt::RefString {
+        git::fmt::RefString::try_from(refname).unwrap()
+    }
+
+    #[test]
+    fn from_fetch() {
+        let oid = git::Oid::from_str(PATCH).unwrap();
+        let updated = [
+            RefUpdate::Created {
+                name: name(&format!(
+                    "refs/namespaces/{}/refs/cobs/xyz.radicle.patch/{PATCH}",
+                    nid()
+                )),
+                oid,
+            },
+            // The same patch under another remote is reported once.
+            RefUpdate::Updated {
+                name: name(&format!("refs/cobs/xyz.radicle.patch/{PATCH}")),
+                old: oid,
+                new: oid,
+            },
+            RefUpdate::Skipped {
+                name: name(&format!("refs/cobs/xyz.radicle.issue/{ISSUE}")),
+                oid,
+            },
+            RefUpdate::Updated {
+                name: name("refs/heads/master"),
+                old: oid,
+                new: oid,
+            },
+        ];
+
+        assert_eq!(
+            Event::from_fetch(rid(), nid(), &updated),
+            vec![
+                Event::RefsFetched {
+                    rid: rid(),
+                    remote: nid(),
+                },
+                Event::PatchUpdated {
+                    rid: rid(),
+                    id: PATCH.parse().unwrap(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_update() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cobs.db");
+        let db = sql::Connection::open(&path).unwrap();
+        db.execute(
+            "CREATE TABLE patches (id TEXT PRIMARY KEY, repo TEXT NOT NULL, patch TEXT NOT NULL);
+             CREATE TABLE issues (id TEXT PRIMARY KEY, repo TEXT NOT NULL, issue TEXT NOT NULL);",
+        )
+        .unwrap();
+        db.execute(format!(
+            "INSERT INTO patches VALUES ('{PATCH}', '{}', '{{\"title\":\"a\"}}');
+             INSERT INTO issues VALUES ('{ISSUE}', '{}', '{{\"title\":\"b\"}}');",
+            rid(),
+            rid()
+        ))
+        .unwrap();
+        let cobs = Sqlite::reader(&path).unwrap();
+        let mut snapshot = Snapshot::new(&cobs).unwrap();
+
+        assert!(snapshot.update(&cobs).unwrap().is_empty());
+
+        db.execute(format!(
+            "UPDATE patches SET patch = '{{\"title\":\"c\"}}' WHERE id = '{PATCH}';
+             DELETE FROM issues WHERE id = '{ISSUE}';"
+        ))
+        .unwrap();
+        let mut events = snapshot.update(&cobs).unwrap();
+        events.sort_by_key(|event| format!("{event:?}"));
+
+        assert_eq!(
+            events,
+            vec![
+                Event::IssueUpdated {
+                    rid: rid(),
+                    id: ObjectId::from_str(ISSUE).unwrap(),
+                },
+                Event::PatchUpdated {
+                    rid: rid(),
+                    id: ObjectId::from_str(PATCH).unwrap(),
+                },
+            ]
+        );
+        assert!(snapshot.update(&cobs).unwrap().is_empty());
+    }
+}
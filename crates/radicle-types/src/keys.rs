@@ -0,0 +1,164 @@
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use ts_rs::TS;
+use zeroize::Zeroizing;
+
+use radicle::crypto::SecretKey;
+use radicle::identity::Did;
+use radicle::node::NodeId;
+
+/// The profile's key pair, as far as it's safe to show.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "keys/")]
+pub struct KeyInfo {
+    #[ts(as = "String")]
+    pub did: Did,
+    #[ts(as = "String")]
+    pub nid: NodeId,
+    /// The SHA-256 fingerprint, the way `ssh-keygen -l` prints it.
+    pub fingerprint: String,
+    /// Whether the secret key is protected by a passphrase.
+    pub encrypted: bool,
+}
+
+/// A decrypted secret key kept in memory, to sign without an ssh-agent. The
+/// key is forgotten once it went unused for the idle timeout, and wiped from
+/// memory when dropped. Cloning shares the key.
+#[derive(Clone, Default)]
+pub struct KeyCache(Arc<Shared>);
+
+#[derive(Default)]
+struct Shared {
+    cache: Mutex<Cache>,
+    /// Wakes the thread waiting to drop the key when the key or the timeout
+    /// changes.
+    changed: Condvar,
+}
+
+struct Cache {
+    timeout: Duration,
+    key: Option<Unlocked>,
+    /// Whether a thread is waiting to drop the key. There's at most one, as
+    /// it follows the key across unlocks.
+    expiring: bool,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self {
+            timeout: KeyCache::DEFAULT_TIMEOUT,
+            key: None,
+            expiring: false,
+        }
+    }
+}
+
+struct Unlocked {
+    nid: NodeId,
+    secret: Zeroizing<SecretKey>,
+    last_used: Instant,
+}
+
+impl KeyCache {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+    /// Keep `secret`, the key of `nid`, until it's idle for the timeout.
+    pub fn unlock(&self, nid: NodeId, secret: Zeroizing<SecretKey>) {
+        let mut cache = self.lock();
+        cache.key = Some(Unlocked {
+            nid,
+            secret,
+            last_used: Instant::now(),
+        });
+        if cache.expiring {
+            self.0.changed.notify_all();
+            return;
+        }
+        cache.expiring = true;
+        drop(cache);
+
+        let cache = self.clone();
+        thread::spawn(move || cache.expire());
+    }
+
+    /// Forget the key right away.
+    pub fn forget(&self) {
+        self.lock().key = None;
+        self.0.changed.notify_all();
+    }
+
+    /// The key of `nid`, if it's unlocked. Using it resets the idle timeout.
+    pub fn get(&self, nid: &NodeId) -> Option<Zeroizing<SecretKey>> {
+        let mut cache = self.lock();
+        let timeout = cache.timeout;
+        let key = cache.key.as_mut()?;
+        if key.last_used.elapsed() >= timeout {
+            cache.key = None;
+            return None;
+        }
+        if key.nid != *nid {
+            return None;
+        }
+        key.last_used = Instant::now();
+
+        Some(key.secret.clone())
+    }
+
+    pub fn is_unlocked(&self, nid: &NodeId) -> bool {
+        let cache = self.lock();
+
+        cache
+            .key
+            .as_ref()
+            .is_some_and(|key| key.nid == *nid && key.last_used.elapsed() < cache.timeout)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.lock().timeout
+    }
+
+    pub fn set_timeout(&self, timeout: Duration) {
+        self.lock().timeout = timeout;
+        self.0.changed.notify_all();
+    }
+
+    /// Whether a thread is waiting to drop the key.
+    #[cfg(test)]
+    pub(crate) fn is_expiring(&self) -> bool {
+        self.lock().expiring
+    }
+
+    /// Wait for the key to go idle and drop it, so it doesn't stay in memory
+    /// until the next time it's asked for.
+    fn expire(&self) {
+        let mut cache = self.lock();
+        loop {
+            let timeout = cache.timeout;
+            let Some(key) = &cache.key else {
+                cache.expiring = false;
+                return;
+            };
+            let idle = key.last_used.elapsed();
+            if idle >= timeout {
+                cache.key = None;
+                cache.expiring = false;
+                return;
+            }
+            cache = self
+                .0
+                .changed
+                .wait_timeout(cache, timeout - idle)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cache> {
+        self.0.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -1,37 +1,97 @@
-use traits::Profile;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use radicle::crypto::ssh::keystore::MemorySigner;
+use radicle::node::device::{BoxedDevice, Device};
+
+use error::Error;
 use traits::cobs::Cobs;
+use traits::diagnostics::Diagnostics;
+use traits::identity::{Identities, IdentitiesMut};
+use traits::inbox::Inbox;
 use traits::issue::{Issues, IssuesMut};
 use traits::job::Jobs;
+use traits::keys::Keys;
+use traits::node::NodeInfo;
 use traits::patch::{Patches, PatchesMut};
+use traits::policy::Policies;
 use traits::repo::Repo;
 use traits::thread::Thread;
+use traits::{Profile, ProfileMut};
 
 pub mod cobs;
 pub mod config;
+pub mod diagnostics;
 pub mod diff;
 pub mod domain;
 pub mod error;
+pub mod events;
+pub mod keys;
+pub mod node;
 pub mod outbound;
+pub mod policy;
+pub mod profiles;
 pub mod repo;
 pub mod source;
 pub mod test;
 pub mod traits;
 
+/// Clones share the profile and the unlocked key, so replacing either is
+/// seen by every command.
 #[derive(Clone)]
 pub struct AppState {
-    pub profile: radicle::Profile,
+    profile: Arc<RwLock<radicle::Profile>>,
+    keys: keys::KeyCache,
+}
+
+impl AppState {
+    pub fn new(profile: radicle::Profile) -> Self {
+        Self {
+            profile: Arc::new(RwLock::new(profile)),
+            keys: keys::KeyCache::default(),
+        }
+    }
+
+    /// The secret key kept in memory for when there's no ssh-agent.
+    pub fn keys(&self) -> &keys::KeyCache {
+        &self.keys
+    }
 }
 
 impl Repo for AppState {}
 impl Thread for AppState {}
 impl Cobs for AppState {}
+impl Diagnostics for AppState {}
+impl Identities for AppState {}
+impl IdentitiesMut for AppState {}
+impl Inbox for AppState {}
 impl Issues for AppState {}
 impl IssuesMut for AppState {}
 impl Jobs for AppState {}
+impl Keys for AppState {}
+impl NodeInfo for AppState {}
 impl Patches for AppState {}
 impl PatchesMut for AppState {}
+impl Policies for AppState {}
 impl Profile for AppState {
     fn profile(&self) -> radicle::Profile {
-        self.profile.clone()
+        self.profile
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Signs with the key unlocked in memory if there is one, so that no
+    /// ssh-agent is needed.
+    fn signer(&self) -> Result<BoxedDevice, Error> {
+        let profile = self.profile();
+        match self.keys.get(&profile.public_key) {
+            Some(secret) => Ok(Device::from(MemorySigner::from_secret(secret)).boxed()),
+            None => Ok(profile.signer()?),
+        }
+    }
+}
+impl ProfileMut for AppState {
+    fn modify_profile<T>(&self, f: impl FnOnce(&mut radicle::Profile) -> T) -> T {
+        f(&mut self.profile.write().unwrap_or_else(PoisonError::into_inner))
     }
 }
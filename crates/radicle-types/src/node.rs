@@ -0,0 +1,142 @@
+use std::net;
+
+use serde::Serialize;
+use ts_rs::TS;
+
+use radicle::identity::RepoId;
+use radicle::node;
+use radicle::node::{Alias, AliasStore};
+
+/// What the node is up to, as far as its control socket tells.
+#[derive(Serialize, TS)]
+#[serde(tag = "status", rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "node/")]
+pub enum Status {
+    /// Nothing answers on the control socket.
+    Stopped,
+    #[serde(rename_all = "camelCase")]
+    Running {
+        sessions: Vec<Session>,
+        config: Config,
+        /// How many (repository, seed) pairs the routing table holds.
+        routing_entries: usize,
+        /// The repositories we announce to the network.
+        #[ts(as = "Vec<String>")]
+        inventory: Vec<RepoId>,
+    },
+}
+
+/// A connection to another node.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "node/")]
+pub struct Session {
+    #[ts(as = "String")]
+    pub nid: node::NodeId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub alias: Option<Alias>,
+    #[ts(as = "String")]
+    pub addr: node::Address,
+    pub link: Link,
+    pub state: SessionState,
+}
+
+impl Session {
+    pub fn new(session: node::Session, aliases: &impl AliasStore) -> Self {
+        Self {
+            alias: aliases.alias(&session.nid),
+            nid: session.nid,
+            addr: session.addr,
+            link: session.link.into(),
+            state: session.state.into(),
+        }
+    }
+}
+
+/// Who opened the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "node/")]
+pub enum Link {
+    Inbound,
+    Outbound,
+}
+
+impl From<node::Link> for Link {
+    fn from(value: node::Link) -> Self {
+        match value {
+            node::Link::Inbound => Self::Inbound,
+            node::Link::Outbound => Self::Outbound,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "node/")]
+pub enum SessionState {
+    /// Not yet dialed.
+    Initial,
+    /// Dialed, waiting for the handshake.
+    Attempted,
+    Connected,
+    /// Waiting to reconnect.
+    Disconnected,
+}
+
+impl From<node::State> for SessionState {
+    fn from(value: node::State) -> Self {
+        match value {
+            node::State::Initial => Self::Initial,
+            node::State::Attempted => Self::Attempted,
+            node::State::Connected { .. } => Self::Connected,
+            node::State::Disconnected { .. } => Self::Disconnected,
+        }
+    }
+}
+
+/// The network settings the node runs with.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "node/")]
+pub struct Config {
+    #[ts(as = "Vec<String>")]
+    pub listen: Vec<net::SocketAddr>,
+    /// Addresses other nodes are told to reach us on.
+    #[ts(as = "Vec<String>")]
+    pub external_addresses: Vec<node::Address>,
+    /// Tor settings, as they appear in the node configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(type = "unknown", optional)]
+    pub onion: Option<serde_json::Value>,
+    /// I2P settings, as they appear in the node configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(type = "unknown", optional)]
+    pub i2p: Option<serde_json::Value>,
+}
+
+impl From<node::config::Config> for Config {
+    fn from(config: node::config::Config) -> Self {
+        // The shape of the overlay network settings depends on which
+        // transports `radicle` was built with, so they are passed through.
+        let mut raw = serde_json::to_value(&config).unwrap_or_default();
+        let mut overlay = |key: &str| {
+            raw.get_mut(key)
+                .map(serde_json::Value::take)
+                .filter(|value| !value.is_null())
+        };
+
+        Self {
+            onion: overlay("onion"),
+            i2p: overlay("i2p"),
+            listen: config.listen,
+            external_addresses: config.external_addresses,
+        }
+    }
+}
@@ -1 +1,3 @@
+pub mod inbox;
+pub mod search;
 pub mod sqlite;
@@ -0,0 +1,435 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time;
+
+use radicle::{git, identity};
+use radicle_localtime::LocalTime;
+use sqlite as sql;
+
+use crate::domain::inbox::models::notification::{
+    self, ListNotificationsError, MarkNotificationsError, NotificationState, SetStatusNotifications,
+};
+use crate::domain::inbox::traits::InboxStorage;
+use crate::error::Error;
+use crate::outbound::sqlite::Sqlite;
+
+/// The node's notifications, with their read, snoozed and done states.
+///
+/// The notifications database is owned by the node and opened read-only, so
+/// the states live in a database of the app's own. They are keyed by
+/// notification id along with the notification's timestamp, so a state never
+/// carries over to a later notification the node stores under a reused id.
+#[derive(Clone)]
+pub struct InboxStore {
+    db: Arc<sql::ConnectionThreadSafe>,
+    /// Held while a transaction is open on `db`. Clones share the connection,
+    /// and SQLite doesn't nest `BEGIN` on one connection.
+    transaction: Arc<Mutex<()>>,
+    notifications: Sqlite,
+}
+
+/// A recorded state, with the timestamp of the notification it was set on.
+struct Record {
+    timestamp: i64,
+    state: NotificationState,
+    until: Option<i64>,
+}
+
+impl Record {
+    /// The state of a notification with `timestamp`, as of `now`.
+    fn state(&self, timestamp: i64, now: i64) -> NotificationState {
+        if self.timestamp != timestamp {
+            return NotificationState::Unread;
+        }
+        match (self.state, self.until) {
+            (NotificationState::Snoozed, Some(until)) if until <= now => NotificationState::Unread,
+            (state, _) => state,
+        }
+    }
+}
+
+impl InboxStore {
+    /// How long to wait for the database lock to be released before failing.
+    const DB_TIMEOUT: time::Duration = time::Duration::from_secs(3);
+
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS states (
+            id INTEGER PRIMARY KEY,
+            timestamp INTEGER NOT NULL,
+            state TEXT NOT NULL,
+            until INTEGER
+        );";
+
+    /// Opens or creates the state database at `path`, for the node's
+    /// `notifications`.
+    pub fn open<P: AsRef<Path>>(path: P, notifications: Sqlite) -> Result<Self, Error> {
+        let mut db = sql::Connection::open_thread_safe(path)?;
+        db.set_busy_timeout(Self::DB_TIMEOUT.as_millis() as usize)?;
+        db.execute(Self::SCHEMA)?;
+
+        Ok(Self {
+            db: Arc::new(db),
+            transaction: Arc::default(),
+            notifications,
+        })
+    }
+
+    fn records(&self) -> Result<BTreeMap<i64, Record>, ListNotificationsError> {
+        let stmt = self
+            .db
+            .prepare("SELECT id, timestamp, state, until FROM states")?;
+        let mut records = BTreeMap::new();
+
+        for row in stmt.into_iter() {
+            let row = row?;
+            let Ok(state) = row.try_read::<&str, _>("state")?.parse() else {
+                continue;
+            };
+            records.insert(
+                row.try_read::<i64, _>("id")?,
+                Record {
+                    timestamp: row.try_read::<i64, _>("timestamp")?,
+                    state,
+                    until: row.try_read::<Option<i64>, _>("until")?,
+                },
+            );
+        }
+
+        Ok(records)
+    }
+
+    /// The notifications in one of `states`, grouped like the node's.
+    fn groups(
+        &self,
+        params: &notification::RepoGroupParams,
+        states: &[NotificationState],
+    ) -> Result<Vec<(identity::RepoId, notification::RepoGroup)>, ListNotificationsError> {
+        let records = self.records()?;
+        let now = LocalTime::now().as_millis() as i64;
+
+        Ok(self
+            .node_groups(params)?
+            .into_iter()
+            .filter_map(|(rid, group)| {
+                let group = group
+                    .into_iter()
+                    .filter_map(|(reference, rows)| {
+                        let rows = rows
+                            .into_iter()
+                            .filter_map(|mut row| {
+                                let timestamp = row.timestamp.as_millis() as i64;
+                                row.state = records
+                                    .get(&(row.row_id as i64))
+                                    .map(|r| r.state(timestamp, now))
+                                    .unwrap_or_default();
+
+                                states.contains(&row.state).then_some(row)
+                            })
+                            .collect::<Vec<_>>();
+
+                        (!rows.is_empty()).then_some((reference, rows))
+                    })
+                    .collect::<Vec<_>>();
+
+                (!group.is_empty()).then_some((rid, group))
+            })
+            .collect())
+    }
+
+    /// The notifications as the node stores them, grouped by repository and
+    /// the object they are about.
+    fn node_groups(
+        &self,
+        params: &notification::RepoGroupParams,
+    ) -> Result<Vec<(identity::RepoId, notification::RepoGroup)>, ListNotificationsError> {
+        let repos_clause = match &params.repos {
+            Some(repos) if !repos.is_empty() => {
+                let placeholders: Vec<String> =
+                    (1..=repos.len()).map(|i| format!("?{}", i)).collect();
+                format!("AND repo IN ({})", placeholders.join(","))
+            }
+            _ => String::from(""),
+        };
+
+        let query = format!(
+            "SELECT repo, ref, substr(ref, 66) ref_without_namespace,
+                json_group_array(
+                    json_object(
+                        'row_id', rowid,
+                        'timestamp', timestamp,
+                        'remote', substr(ref, 17, 48),
+                        'old', old,
+                        'new', new
+                    )
+                ) as value,
+                MAX(timestamp) AS latest_timestamp
+            FROM 'repository-notifications'
+            WHERE new NOT NULL
+              AND (ref LIKE '%cobs/xyz.radicle.patch%'
+                   OR ref LIKE '%cobs/xyz.radicle.issue%')
+            {}
+            GROUP BY repo, ref_without_namespace
+            ORDER BY latest_timestamp DESC",
+            repos_clause
+        );
+
+        let mut stmt = self.notifications.db.prepare(&query)?;
+
+        if let Some(repos) = &params.repos
+            && !repos.is_empty()
+        {
+            for (i, repo) in repos.iter().enumerate() {
+                stmt.bind((i + 1, repo))?;
+            }
+        }
+
+        let mut result: Vec<(identity::RepoId, notification::RepoGroup)> = Vec::new();
+        let mut current_repo: Option<identity::RepoId> = None;
+        let mut current_group: notification::RepoGroup = Vec::new();
+
+        for row_result in stmt.into_iter() {
+            let row = row_result?;
+            let repo_id = row.try_read::<identity::RepoId, _>("repo")?;
+            let refstr = row.try_read::<&str, _>("ref")?;
+            let value = row.try_read::<&str, _>("value")?;
+            let items = serde_json::from_str::<Vec<notification::NotificationRow>>(value)?;
+            let (_, reference) = git::parse_ref::<String>(refstr)?;
+
+            if let Some(current) = current_repo {
+                if current != repo_id {
+                    result.push((current, std::mem::take(&mut current_group)));
+                    current_repo = Some(repo_id);
+                }
+            } else {
+                current_repo = Some(repo_id);
+            }
+
+            current_group.push((reference.to_owned(), items));
+        }
+
+        if let Some(repo) = current_repo
+            && !current_group.is_empty()
+        {
+            result.push((repo, current_group));
+        }
+
+        Ok(result)
+    }
+
+    fn set(
+        &self,
+        id: i64,
+        timestamp: i64,
+        state: NotificationState,
+        until: Option<i64>,
+    ) -> Result<(), MarkNotificationsError> {
+        if state == NotificationState::Unread {
+            let mut stmt = self.db.prepare("DELETE FROM states WHERE id = ?1")?;
+            stmt.bind((1, id))?;
+            stmt.next()?;
+
+            return Ok(());
+        }
+
+        let mut stmt = self.db.prepare(
+            "INSERT INTO states (id, timestamp, state, until) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (id) DO UPDATE SET
+                timestamp = excluded.timestamp,
+                state = excluded.state,
+                until = excluded.until",
+        )?;
+        stmt.bind((1, id))?;
+        stmt.bind((2, timestamp))?;
+        stmt.bind((3, state.as_str()))?;
+        stmt.bind((4, until.map_or(sql::Value::Null, sql::Value::Integer)))?;
+        stmt.next()?;
+
+        Ok(())
+    }
+}
+
+impl InboxStorage for InboxStore {
+    fn counts_by_repo(
+        &self,
+    ) -> Result<
+        impl Iterator<Item = Result<notification::CountByRepo, ListNotificationsError>>,
+        ListNotificationsError,
+    > {
+        let groups = self.groups(&Default::default(), &[NotificationState::Unread])?;
+
+        Ok(groups
+            .into_iter()
+            .map(|(rid, group)| Ok((rid, group.len()))))
+    }
+
+    fn notification_count(&self) -> Result<usize, ListNotificationsError> {
+        let groups = self.groups(&Default::default(), &[NotificationState::Unread])?;
+
+        Ok(groups.iter().map(|(_, group)| group.len()).sum())
+    }
+
+    fn repo_group(
+        &self,
+        params: notification::RepoGroupParams,
+    ) -> Result<Vec<(identity::RepoId, notification::RepoGroup)>, ListNotificationsError> {
+        let states = params
+            .states
+            .clone()
+            .unwrap_or_else(|| NotificationState::VISIBLE.to_vec());
+
+        self.groups(&params, &states)
+    }
+
+    fn mark(
+        &self,
+        notifications: SetStatusNotifications,
+        state: NotificationState,
+        until: Option<u64>,
+    ) -> Result<(), MarkNotificationsError> {
+        let until = match (state, until) {
+            (NotificationState::Snoozed, Some(until)) if until > LocalTime::now().as_millis() => {
+                Some(until as i64)
+            }
+            (NotificationState::Snoozed, _) => return Err(MarkNotificationsError::SnoozeTime),
+            _ => None,
+        };
+        let (params, ids) = match notifications {
+            SetStatusNotifications::Ids(ids) => (
+                Default::default(),
+                Some(ids.into_iter().map(|id| id as i64).collect::<BTreeSet<_>>()),
+            ),
+            SetStatusNotifications::Repo(rid) => (
+                notification::RepoGroupParams {
+                    repos: Some(vec![rid]),
+                    ..Default::default()
+                },
+                None,
+            ),
+            SetStatusNotifications::All => (Default::default(), None),
+        };
+        let rows = self
+            .node_groups(&params)?
+            .into_iter()
+            .flat_map(|(_, group)| group)
+            .flat_map(|(_, rows)| rows)
+            .map(|row| (row.row_id as i64, row.timestamp.as_millis() as i64))
+            .filter(|(id, _)| ids.as_ref().is_none_or(|ids| ids.contains(id)))
+            .collect::<Vec<_>>();
+
+        let _transaction = self
+            .transaction
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.db.execute("BEGIN")?;
+        let result = rows
+            .into_iter()
+            .try_for_each(|(id, timestamp)| self.set(id, timestamp, state, until));
+
+        match result {
+            Ok(()) => {
+                self.db.execute("COMMIT")?;
+
+                Ok(())
+            }
+            Err(err) => {
+                self.db.execute("ROLLBACK")?;
+
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use radicle::identity::RepoId;
+    use sqlite as sql;
+
+    use super::{InboxStore, NotificationState, Record};
+    use crate::domain::inbox::models::notification::SetStatusNotifications;
+    use crate::domain::inbox::traits::InboxStorage;
+    use crate::outbound::sqlite::Sqlite;
+
+    const NID: &str = "z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi";
+    const OID: &str = "e7a8b5e4ab6c2a4e1b7b3a1ec9b8f5a6d2c1b0a9";
+
+    /// A notifications database laid out like the node's, with one
+    /// notification on an issue of each of `repos`.
+    fn notifications(path: &std::path::Path, repos: &[RepoId]) -> Sqlite {
+        let db = sql::Connection::open(path).unwrap();
+        db.execute(
+            "CREATE TABLE 'repository-notifications' (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                repo TEXT NOT NULL,
+                ref TEXT NOT NULL,
+                old TEXT,
+                new TEXT,
+                timestamp INTEGER NOT NULL
+            )",
+        )
+        .unwrap();
+        for (i, rid) in repos.iter().enumerate() {
+            db.execute(format!(
+                "INSERT INTO 'repository-notifications' (repo, ref, old, new, timestamp)
+                 VALUES ('{rid}', 'refs/namespaces/{NID}/refs/cobs/xyz.radicle.issue/{OID}', NULL, '{OID}', {})",
+                1_700_000_000_000u64 + i as u64
+            ))
+            .unwrap();
+        }
+
+        Sqlite::reader(path).unwrap()
+    }
+
+    #[test]
+    fn snoozed_notifications_wake_up() {
+        let record = Record {
+            timestamp: 10,
+            state: NotificationState::Snoozed,
+            until: Some(100),
+        };
+
+        assert_eq!(record.state(10, 99), NotificationState::Snoozed);
+        assert_eq!(record.state(10, 100), NotificationState::Unread);
+        // A reused id belongs to a different notification.
+        assert_eq!(record.state(11, 99), NotificationState::Unread);
+    }
+
+    #[test]
+    fn counts_only_unread() {
+        let tmp = tempfile::tempdir().unwrap();
+        let read = "rad:z3gqcJUoA1n9HaHKufZs5FCSGazv5"
+            .parse::<RepoId>()
+            .unwrap();
+        let unread = "rad:z4V1sjrXqjvFdnCUbxPFqd5p4DtH5"
+            .parse::<RepoId>()
+            .unwrap();
+        let store = InboxStore::open(
+            tmp.path().join("inbox.db"),
+            notifications(&tmp.path().join("notifications.db"), &[read, unread]),
+        )
+        .unwrap();
+
+        assert_eq!(store.notification_count().unwrap(), 2);
+        assert_eq!(store.counts_by_repo().unwrap().count(), 2);
+
+        store
+            .mark(
+                SetStatusNotifications::Repo(read),
+                NotificationState::Read,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(store.notification_count().unwrap(), 1);
+        let counts = store
+            .counts_by_repo()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(counts, vec![(unread, 1)]);
+        // Read notifications are still listed, they just don't count.
+        assert_eq!(store.repo_group(Default::default()).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,581 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time;
+
+use radicle::cob::{self, ObjectId};
+use radicle::identity::{self, Did};
+use radicle::issue::Issue;
+use radicle::patch::Patch;
+use sqlite as sql;
+
+use crate::domain::search::models::search::{Kind, Match, SearchError, SearchQuery};
+use crate::domain::search::traits::SearchStorage;
+use crate::error::Error;
+use crate::outbound::sqlite::{Sqlite, digest, placeholders};
+
+/// Full-text index over the titles, descriptions and comments of the cached
+/// issues and patches.
+///
+/// The COB cache is owned by the node and opened read-only, so the index
+/// lives in a database of its own and is brought up to date from the cache
+/// before every search. Only objects whose cached JSON changed since they
+/// were last indexed are re-read.
+#[derive(Clone)]
+pub struct SearchIndex {
+    db: Arc<sql::ConnectionThreadSafe>,
+    cobs: Sqlite,
+    /// The cache's `data_version` as of the last refresh. Held for as long
+    /// as the index is read or written, so that a refresh's transaction on
+    /// the shared connection never takes in another call's statements.
+    version: Arc<Mutex<Option<i64>>>,
+}
+
+/// A piece of text to index, with the object it belongs to.
+struct Document {
+    kind: Kind,
+    rid: identity::RepoId,
+    id: ObjectId,
+    entry: Option<String>,
+    author: Did,
+    labels: String,
+    cob_title: String,
+    title: String,
+    body: String,
+}
+
+impl SearchIndex {
+    /// How long to wait for the database lock to be released before failing.
+    const DB_TIMEOUT: time::Duration = time::Duration::from_secs(3);
+
+    const SCHEMA: &str = "
+        CREATE VIRTUAL TABLE IF NOT EXISTS documents USING fts5(
+            title,
+            body,
+            kind UNINDEXED,
+            repo UNINDEXED,
+            cob UNINDEXED,
+            entry UNINDEXED,
+            author UNINDEXED,
+            labels UNINDEXED,
+            cob_title UNINDEXED,
+            tokenize = 'porter unicode61'
+        );
+        CREATE TABLE IF NOT EXISTS indexed (
+            type TEXT NOT NULL,
+            repo TEXT NOT NULL,
+            cob TEXT NOT NULL,
+            digest INTEGER NOT NULL,
+            PRIMARY KEY (type, repo, cob)
+        );";
+
+    /// Opens or creates the index at `path`, fed from the COB cache `cobs`.
+    pub fn open<P: AsRef<Path>>(path: P, cobs: Sqlite) -> Result<Self, Error> {
+        let mut db = sql::Connection::open_thread_safe(path)?;
+        db.set_busy_timeout(Self::DB_TIMEOUT.as_millis() as usize)?;
+        db.execute(Self::SCHEMA)?;
+
+        Ok(Self {
+            db: Arc::new(db),
+            cobs,
+            version: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Re-indexes every object that changed in the COB cache since the last
+    /// refresh, and drops the ones that are gone.
+    pub fn refresh(&self) -> Result<(), Error> {
+        self.refresh_locked(&mut self.lock())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<i64>> {
+        // The version is only recorded once a refresh commits, so it stays
+        // accurate even if a previous holder panicked.
+        self.version
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// [`SearchIndex::refresh`], with the lock already held.
+    fn refresh_locked(&self, version: &mut Option<i64>) -> Result<(), Error> {
+        let current = self.cobs.data_version()?;
+        if *version == Some(current) {
+            return Ok(());
+        }
+
+        let mut stale = self.indexed()?;
+        self.db.execute("BEGIN")?;
+        let result = self.cobs.for_each_cob(|type_name, rid, id, json| {
+            let digest = digest(json) as i64;
+            let key = (type_name.to_owned(), rid, id);
+            if stale.remove(&key) == Some(digest) {
+                return Ok(());
+            }
+            self.remove(&key)?;
+            self.insert(type_name, rid, id, json)?;
+            self.mark_indexed(&key, digest)
+        });
+        let result = result.and_then(|()| stale.keys().try_for_each(|key| self.remove(key)));
+
+        match result {
+            Ok(()) => {
+                self.db.execute("COMMIT")?;
+                *version = Some(current);
+
+                Ok(())
+            }
+            Err(err) => {
+                self.db.execute("ROLLBACK")?;
+
+                Err(err)
+            }
+        }
+    }
+
+    /// The digest each object had when it was last indexed.
+    fn indexed(&self) -> Result<BTreeMap<(String, identity::RepoId, ObjectId), i64>, Error> {
+        let stmt = self
+            .db
+            .prepare("SELECT type, repo, cob, digest FROM indexed")?;
+        let mut indexed = BTreeMap::new();
+
+        for row in stmt.into_iter() {
+            let row = row?;
+            let Ok(id) = ObjectId::from_str(row.try_read::<&str, _>("cob")?) else {
+                continue;
+            };
+            indexed.insert(
+                (
+                    row.try_read::<&str, _>("type")?.to_owned(),
+                    row.try_read::<identity::RepoId, _>("repo")?,
+                    id,
+                ),
+                row.try_read::<i64, _>("digest")?,
+            );
+        }
+
+        Ok(indexed)
+    }
+
+    fn mark_indexed(
+        &self,
+        (type_name, rid, id): &(String, identity::RepoId, ObjectId),
+        digest: i64,
+    ) -> Result<(), Error> {
+        let mut stmt = self.db.prepare(
+            "INSERT INTO indexed (type, repo, cob, digest) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (type, repo, cob) DO UPDATE SET digest = excluded.digest",
+        )?;
+        stmt.bind((1, type_name.as_str()))?;
+        stmt.bind((2, rid))?;
+        stmt.bind((3, id.to_string().as_str()))?;
+        stmt.bind((4, digest))?;
+        stmt.next()?;
+
+        Ok(())
+    }
+
+    fn remove(
+        &self,
+        (type_name, rid, id): &(String, identity::RepoId, ObjectId),
+    ) -> Result<(), Error> {
+        let id = id.to_string();
+        let mut stmt = self
+            .db
+            .prepare("DELETE FROM documents WHERE repo = ?1 AND cob = ?2")?;
+        stmt.bind((1, rid))?;
+        stmt.bind((2, id.as_str()))?;
+        stmt.next()?;
+
+        let mut stmt = self
+            .db
+            .prepare("DELETE FROM indexed WHERE type = ?1 AND repo = ?2 AND cob = ?3")?;
+        stmt.bind((1, type_name.as_str()))?;
+        stmt.bind((2, rid))?;
+        stmt.bind((3, id.as_str()))?;
+        stmt.next()?;
+
+        Ok(())
+    }
+
+    fn insert(
+        &self,
+        type_name: &str,
+        rid: identity::RepoId,
+        id: ObjectId,
+        json: &str,
+    ) -> Result<(), Error> {
+        // Objects the cache holds but this build can't read are left out of
+        // the index rather than failing the whole refresh.
+        let documents = match type_name {
+            "xyz.radicle.issue" => match serde_json::from_str::<Issue>(json) {
+                Ok(issue) => issue_documents(rid, id, &issue),
+                Err(err) => {
+                    log::debug!("search: skipping unreadable issue {id} in {rid}: {err}");
+                    return Ok(());
+                }
+            },
+            "xyz.radicle.patch" => match serde_json::from_str::<Patch>(json) {
+                Ok(patch) => patch_documents(rid, id, &patch),
+                Err(err) => {
+                    log::debug!("search: skipping unreadable patch {id} in {rid}: {err}");
+                    return Ok(());
+                }
+            },
+            _ => return Ok(()),
+        };
+
+        let mut stmt = self.db.prepare(
+            "INSERT INTO documents (title, body, kind, repo, cob, entry, author, labels, cob_title)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        )?;
+        for doc in documents {
+            stmt.reset()?;
+            stmt.bind((1, doc.title.as_str()))?;
+            stmt.bind((2, doc.body.as_str()))?;
+            stmt.bind((3, doc.kind.as_str()))?;
+            stmt.bind((4, &doc.rid))?;
+            stmt.bind((5, doc.id.to_string().as_str()))?;
+            match &doc.entry {
+                Some(entry) => stmt.bind((6, entry.as_str()))?,
+                None => stmt.bind((6, sql::Value::Null))?,
+            }
+            stmt.bind((7, doc.author.to_string().as_str()))?;
+            stmt.bind((8, doc.labels.as_str()))?;
+            stmt.bind((9, doc.cob_title.as_str()))?;
+            stmt.next()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SearchStorage for SearchIndex {
+    fn search(&self, query: &SearchQuery, limit: usize) -> Result<Vec<Match>, SearchError> {
+        let Some(expr) = match_expression(&query.query) else {
+            return Ok(Vec::new());
+        };
+        let mut version = self.lock();
+        self.refresh_locked(&mut version)
+            .map_err(|err| SearchError::Unknown(err.into()))?;
+
+        let mut filters = String::new();
+        let mut params: Vec<sql::Value> = vec![sql::Value::String(expr)];
+        if let Some(rids) = query.rids.as_ref().filter(|r| !r.is_empty()) {
+            let values = rids
+                .iter()
+                .map(|rid| sql::Value::String(rid.to_string()))
+                .collect();
+            filters.push_str(&format!(
+                " AND repo IN ({})",
+                placeholders(values, &mut params)
+            ));
+        }
+        if let Some(kinds) = query.kinds.as_ref().filter(|k| !k.is_empty()) {
+            let values = kinds
+                .iter()
+                .map(|kind| sql::Value::String(kind.as_str().to_owned()))
+                .collect();
+            filters.push_str(&format!(
+                " AND kind IN ({})",
+                placeholders(values, &mut params)
+            ));
+        }
+        if let Some(author) = &query.author {
+            let values = vec![sql::Value::String(author.to_string())];
+            filters.push_str(&format!(
+                " AND author = {}",
+                placeholders(values, &mut params)
+            ));
+        }
+        if let Some(label) = &query.label {
+            // Labels can't contain whitespace, so a space-separated list is
+            // unambiguous.
+            let label = label
+                .as_str()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            let values = vec![sql::Value::String(format!("% {label} %"))];
+            filters.push_str(&format!(
+                " AND (' ' || labels || ' ') LIKE {} ESCAPE '\\'",
+                placeholders(values, &mut params)
+            ));
+        }
+
+        let mut stmt = self.db.prepare(format!(
+            "SELECT kind, repo, cob, entry, author, cob_title,
+                 snippet(documents, -1, '<mark>', '</mark>', '…', 24) AS snippet,
+                 bm25(documents, 4.0, 1.0) AS rank
+             FROM documents
+             WHERE documents MATCH ?1
+             {filters}
+             ORDER BY rank
+             LIMIT {limit}"
+        ))?;
+        for (i, value) in params.into_iter().enumerate() {
+            stmt.bind((i + 1, value))?;
+        }
+
+        stmt.into_iter()
+            .map(|row| {
+                let row = row?;
+                let entry = row
+                    .try_read::<Option<&str>, _>("entry")?
+                    .map(cob::EntryId::from_str)
+                    .transpose()
+                    .map_err(|err| SearchError::Unknown(err.into()))?;
+
+                Ok(Match {
+                    kind: Kind::from_str(row.try_read::<&str, _>("kind")?)?,
+                    rid: row.try_read::<identity::RepoId, _>("repo")?,
+                    id: ObjectId::from_str(row.try_read::<&str, _>("cob")?)
+                        .map_err(|err| SearchError::Unknown(err.into()))?,
+                    entry,
+                    author: Did::from_str(row.try_read::<&str, _>("author")?)
+                        .map_err(|err| SearchError::Unknown(err.into()))?,
+                    title: row.try_read::<&str, _>("cob_title")?.to_owned(),
+                    snippet: row.try_read::<&str, _>("snippet")?.to_owned(),
+                    rank: row.try_read::<f64, _>("rank")?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Turns free text into an FTS5 expression matching documents that contain
+/// every word, so user input never trips over the query syntax. The last word
+/// also matches as a prefix, for search-as-you-type.
+fn match_expression(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    if terms.is_empty() {
+        return None;
+    }
+
+    Some(format!("{}*", terms.join(" ")))
+}
+
+fn issue_documents(rid: identity::RepoId, id: ObjectId, issue: &Issue) -> Vec<Document> {
+    let labels = issue
+        .labels()
+        .map(|l| l.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let doc = |entry: &cob::EntryId, author: Did, title: &str, body: &str| Document {
+        kind: Kind::Issue,
+        rid,
+        id,
+        entry: Some(entry.to_string()),
+        author,
+        labels: labels.clone(),
+        cob_title: issue.title().to_owned(),
+        title: title.to_owned(),
+        body: body.to_owned(),
+    };
+    let (root_id, root) = issue.root();
+
+    std::iter::once(doc(
+        root_id,
+        root.author().into(),
+        issue.title(),
+        root.body(),
+    ))
+    .chain(
+        issue
+            .replies()
+            .map(|(reply_id, reply)| doc(reply_id, reply.author().into(), "", reply.body())),
+    )
+    .collect()
+}
+
+fn patch_documents(rid: identity::RepoId, id: ObjectId, patch: &Patch) -> Vec<Document> {
+    let labels = patch
+        .labels()
+        .map(|l| l.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let doc = |entry: Option<String>, author: Did, title: &str, body: &str| Document {
+        kind: Kind::Patch,
+        rid,
+        id,
+        entry,
+        author,
+        labels: labels.clone(),
+        cob_title: patch.title().to_owned(),
+        title: title.to_owned(),
+        body: body.to_owned(),
+    };
+    let mut documents = vec![doc(None, *patch.author().id(), patch.title(), "")];
+
+    for (revision_id, revision) in patch.revisions() {
+        if !revision.description().is_empty() {
+            documents.push(doc(
+                Some(revision_id.to_string()),
+                *revision.author().id(),
+                "",
+                revision.description(),
+            ));
+        }
+        for (comment_id, comment) in revision.discussion().comments() {
+            documents.push(doc(
+                Some(comment_id.to_string()),
+                comment.author().into(),
+                "",
+                comment.body(),
+            ));
+        }
+        for (_, review) in revision.reviews() {
+            let author = review.author().id;
+            if !review.summary().is_empty() {
+                documents.push(doc(
+                    Some(review.id().to_string()),
+                    author,
+                    "",
+                    review.summary(),
+                ));
+            }
+            for (comment_id, comment) in review.comments() {
+                documents.push(doc(
+                    Some(comment_id.to_string()),
+                    comment.author().into(),
+                    "",
+                    comment.body(),
+                ));
+            }
+        }
+    }
+
+    documents
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use radicle::cob::Label;
+    use radicle::cob::cache::COBS_DB_FILE;
+    use radicle::identity::{Did, RepoId};
+
+    use super::{SearchIndex, match_expression};
+    use crate::cobs::CobOptions;
+    use crate::cobs::issue::NewIssue;
+    use crate::domain::search::models::search::{Kind, SearchQuery};
+    use crate::domain::search::traits::SearchStorage;
+    use crate::outbound::sqlite::Sqlite;
+    use crate::traits::Profile as _;
+    use crate::traits::issue::IssuesMut;
+    use crate::{AppState, test};
+
+    fn open_issue(state: &AppState, rid: RepoId, title: &str, label: &str) {
+        state
+            .create_issue(
+                rid,
+                NewIssue {
+                    title: title.to_owned(),
+                    description: String::new(),
+                    labels: vec![Label::new(label).unwrap()],
+                    assignees: vec![],
+                    embeds: vec![],
+                },
+                serde_json::from_str::<CobOptions>(r#"{ "announce": false }"#).unwrap(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn search_filters() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, _) = test::repo(tmp.path());
+        let me = Did::from(state.profile().public_key);
+        open_issue(&state, rid, "Crash on startup", "bug");
+
+        let cobs = Sqlite::reader(state.profile().cobs().join(COBS_DB_FILE)).unwrap();
+        let index = SearchIndex::open(tmp.path().join("search.db"), cobs).unwrap();
+        let search = |query: SearchQuery| index.search(&query, 10).unwrap().len();
+        let crash = || SearchQuery {
+            query: "crash".to_owned(),
+            ..SearchQuery::default()
+        };
+
+        assert_eq!(search(crash()), 1);
+
+        // A refresh picks up what was written to the cache since.
+        open_issue(&state, rid, "Crash on exit", "ux");
+        assert_eq!(search(crash()), 2);
+
+        let other: RepoId = "rad:z4V1sjrXqjvFdnCUbxPFqd5p4DtH5".parse().unwrap();
+        assert_eq!(
+            search(SearchQuery {
+                rids: Some(vec![rid]),
+                ..crash()
+            }),
+            2
+        );
+        assert_eq!(
+            search(SearchQuery {
+                rids: Some(vec![other]),
+                ..crash()
+            }),
+            0
+        );
+        assert_eq!(
+            search(SearchQuery {
+                kinds: Some(vec![Kind::Issue]),
+                ..crash()
+            }),
+            2
+        );
+        assert_eq!(
+            search(SearchQuery {
+                kinds: Some(vec![Kind::Patch]),
+                ..crash()
+            }),
+            0
+        );
+        assert_eq!(
+            search(SearchQuery {
+                author: Some(me),
+                ..crash()
+            }),
+            2
+        );
+        assert_eq!(
+            search(SearchQuery {
+                author: Some(
+                    "did:key:z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi"
+                        .parse()
+                        .unwrap()
+                ),
+                ..crash()
+            }),
+            0
+        );
+        assert_eq!(
+            search(SearchQuery {
+                label: Some(Label::new("bug").unwrap()),
+                ..crash()
+            }),
+            1
+        );
+        assert_eq!(
+            search(SearchQuery {
+                label: Some(Label::new("bu").unwrap()),
+                ..crash()
+            }),
+            0
+        );
+    }
+
+    #[test]
+    fn match_expression_quotes_terms() {
+        assert_eq!(match_expression("  "), None);
+        assert_eq!(match_expression("crash"), Some("\"crash\"*".to_owned()));
+        assert_eq!(
+            match_expression("seed \"sync NOT"),
+            Some("\"seed\" \"\"\"sync\" \"NOT\"*".to_owned())
+        );
+    }
+}
@@ -1,16 +1,20 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time;
 
+use radicle::cob::{self, ObjectId};
+use radicle::identity;
 use radicle::issue::{Issue, IssueId};
 use radicle::patch::{Patch, PatchId, Status};
-use radicle::{git, identity};
 use sqlite as sql;
 
-use crate::domain::inbox::models::notification;
-use crate::domain::inbox::traits::InboxStorage;
-use crate::domain::issue::models::issue::{ListIssuesError, Status as IssueStatus};
+use crate::cobs::query::{LabelFilter, ListFilter, Order, Sort};
+use crate::cobs::{Cursor, FacetCounts};
+use crate::domain::issue::models::issue::{
+    CountsError as IssueCountsError, IssueCounts, ListIssuesError, Status as IssueStatus,
+};
 use crate::domain::issue::traits::IssueStorage;
 use crate::domain::patch::models::patch::{CountsError, ListPatchesError, PatchCounts, State};
 use crate::domain::patch::traits::PatchStorage;
@@ -34,6 +38,70 @@ impl Sqlite {
 
         Ok(Self { db: Arc::new(db) })
     }
+
+    /// Changes whenever another connection commits to the database, so a
+    /// reader can notice writes without re-reading any table.
+    pub fn data_version(&self) -> Result<i64, Error> {
+        let stmt = self.db.prepare("PRAGMA data_version")?;
+
+        match stmt.into_iter().next() {
+            Some(row) => Ok(row?.try_read::<i64, _>("data_version")?),
+            None => Ok(0),
+        }
+    }
+
+    /// Visits every cached patch and issue with its COB type name, repo, id
+    /// and JSON. Rows with an unparsable id are skipped.
+    pub fn for_each_cob(
+        &self,
+        mut f: impl FnMut(&str, identity::RepoId, ObjectId, &str) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let stmt = self.db.prepare(
+            "SELECT 'xyz.radicle.patch' AS type, repo, id, patch AS cob FROM patches
+             UNION ALL
+             SELECT 'xyz.radicle.issue' AS type, repo, id, issue AS cob FROM issues",
+        )?;
+
+        for row in stmt.into_iter() {
+            let row = row?;
+            let Ok(id) = ObjectId::from_str(row.try_read::<&str, _>("id")?) else {
+                continue;
+            };
+            f(
+                row.try_read::<&str, _>("type")?,
+                row.try_read::<identity::RepoId, _>("repo")?,
+                id,
+                row.try_read::<&str, _>("cob")?,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// A hash of every cached patch and issue, keyed by COB type name, repo
+    /// and id, to tell which objects changed between two reads.
+    pub fn cob_digests(
+        &self,
+    ) -> Result<BTreeMap<(String, identity::RepoId, ObjectId), u64>, Error> {
+        let mut digests = BTreeMap::new();
+        self.for_each_cob(|type_name, rid, id, cob| {
+            digests.insert((type_name.to_owned(), rid, id), digest(cob));
+            Ok(())
+        })?;
+
+        Ok(digests)
+    }
+}
+
+/// 64-bit FNV-1a hash of a cached COB's JSON. Digests are kept in the search
+/// index across app updates, so unlike `DefaultHasher` this must never change.
+pub fn digest(cob: &str) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    cob.bytes().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
 }
 
 impl PatchStorage for Sqlite {
@@ -68,23 +136,9 @@ impl PatchStorage for Sqlite {
         &self,
         rid: identity::RepoId,
     ) -> Result<impl Iterator<Item = (PatchId, Patch)>, ListPatchesError> {
-        let mut stmt = self.db.prepare(
-            "SELECT id, patch, (
-                 SELECT MIN(JSON_EXTRACT(revision.value, '$.timestamp'))
-                 FROM JSON_EACH(JSON_EXTRACT(p.patch, '$.revisions')) AS revision
-             ) AS last_revision_timestamp
-             FROM patches AS p
-             WHERE repo = ?1
-             ORDER BY last_revision_timestamp DESC, id DESC;
-             ",
-        )?;
-        stmt.bind((1, &rid))?;
-        Ok(stmt.into_iter().filter_map(|row| {
-            let row = row.ok()?;
-            let id = PatchId::from_str(row.read::<&str, _>("id")).ok()?;
-            let patch = serde_json::from_str::<Patch>(row.read::<&str, _>("patch")).ok()?;
-            Some((id, patch))
-        }))
+        Ok(self
+            .list_filtered(rid, None, &ListFilter::default(), None)?
+            .map(|(id, patch, _)| (id, patch)))
     }
 
     fn list_by_status(
@@ -92,76 +146,305 @@ impl PatchStorage for Sqlite {
         rid: identity::RepoId,
         status: Status,
     ) -> Result<impl Iterator<Item = (PatchId, Patch)>, ListPatchesError> {
-        let mut stmt = self.db.prepare(
-            "SELECT id, patch, (
-                 SELECT MIN(JSON_EXTRACT(revision.value, '$.timestamp'))
-                 FROM JSON_EACH(JSON_EXTRACT(p.patch, '$.revisions')) AS revision
-             ) AS last_revision_timestamp
-             FROM patches AS p
-             WHERE repo = ?1
-             AND patch->>'$.state.status' = ?2
-             ORDER BY last_revision_timestamp DESC, id DESC;
-             ",
-        )?;
-        stmt.bind((1, &rid))?;
-        stmt.bind((2, sql::Value::String(status.to_string())))?;
-        Ok(stmt.into_iter().filter_map(|row| {
-            let row = row.ok()?;
-            let id = PatchId::from_str(row.read::<&str, _>("id")).ok()?;
-            let patch = serde_json::from_str::<Patch>(row.read::<&str, _>("patch")).ok()?;
-            Some((id, patch))
-        }))
+        Ok(self
+            .list_filtered(rid, Some(status), &ListFilter::default(), None)?
+            .map(|(id, patch, _)| (id, patch)))
+    }
+
+    fn list_filtered(
+        &self,
+        rid: identity::RepoId,
+        status: Option<Status>,
+        filter: &ListFilter,
+        after: Option<&Cursor>,
+    ) -> Result<impl Iterator<Item = (PatchId, Patch, Cursor)>, ListPatchesError> {
+        let status = status.map(|s| s.to_string());
+
+        Ok(self.cobs_by::<Patch>(&CobQuery::PATCHES, rid, status.as_deref(), filter, after)?)
+    }
+
+    fn facets(
+        &self,
+        rid: identity::RepoId,
+        status: Option<Status>,
+    ) -> Result<FacetCounts, CountsError> {
+        let status = status.map(|s| s.to_string());
+
+        self.facet_counts(&CobQuery::PATCHES, rid, status.as_deref())
+            .map_err(CountsError::Unknown)
     }
 }
 
+/// The parts of a filtered issue or patch listing that depend on how the COB
+/// is stored. Expressions refer to the row's JSON as `c.issue` or `c.patch`;
+/// authors and reviewers are compared as DIDs.
+struct CobQuery {
+    table: &'static str,
+    column: &'static str,
+    created: &'static str,
+    last_activity: &'static str,
+    comment_count: &'static str,
+    author: &'static str,
+    /// A subquery listing every reviewer, where the COB has reviews.
+    reviewers: Option<&'static str>,
+}
+
+impl CobQuery {
+    /// The sort key is the root comment's timestamp (the comment without a
+    /// `replyTo`) — the issue's creation time — not the minimum across
+    /// replies, whose author-supplied clocks could otherwise sink an issue
+    /// below its real position. Like for patches, the root comment doesn't
+    /// count as a comment.
+    const ISSUES: Self = Self {
+        table: "issues",
+        column: "issue",
+        created: "(SELECT MIN(JSON_EXTRACT(comment.value, '$.edits[0].timestamp'))
+             FROM JSON_EACH(c.issue, '$.thread.comments') AS comment
+             WHERE JSON_EXTRACT(comment.value, '$.replyTo') IS NULL)",
+        last_activity: "(SELECT MAX(JSON_EXTRACT(comment.value, '$.edits[#-1].timestamp'))
+             FROM JSON_EACH(c.issue, '$.thread.comments') AS comment)",
+        comment_count: "(SELECT COUNT(*)
+             FROM JSON_EACH(c.issue, '$.thread.comments') AS comment
+             WHERE comment.type = 'object'
+             AND JSON_EXTRACT(comment.value, '$.replyTo') IS NOT NULL)",
+        author: "(SELECT 'did:key:' || JSON_EXTRACT(comment.value, '$.author')
+             FROM JSON_EACH(c.issue, '$.thread.comments') AS comment
+             WHERE JSON_EXTRACT(comment.value, '$.replyTo') IS NULL)",
+        reviewers: None,
+    };
+
+    /// A patch is as old as its first revision. Its comments are those of
+    /// every revision's discussion plus every review's code comments,
+    /// matching `Patch::comment_count`.
+    const PATCHES: Self = Self {
+        table: "patches",
+        column: "patch",
+        created: "(SELECT MIN(JSON_EXTRACT(revision.value, '$.timestamp'))
+             FROM JSON_EACH(c.patch, '$.revisions') AS revision)",
+        last_activity: "MAX(
+             (SELECT MAX(JSON_EXTRACT(revision.value, '$.timestamp'))
+              FROM JSON_EACH(c.patch, '$.revisions') AS revision),
+             COALESCE((SELECT MAX(JSON_EXTRACT(comment.value, '$.edits[#-1].timestamp'))
+              FROM JSON_EACH(c.patch, '$.revisions') AS revision,
+                   JSON_EACH(revision.value, '$.discussion.comments') AS comment), 0),
+             COALESCE((SELECT MAX(JSON_EXTRACT(review.value, '$.timestamp'))
+              FROM JSON_EACH(c.patch, '$.revisions') AS revision,
+                   JSON_EACH(revision.value, '$.reviews') AS review), 0))",
+        comment_count: "(SELECT COUNT(*)
+             FROM JSON_EACH(c.patch, '$.revisions') AS revision,
+                  JSON_EACH(revision.value, '$.discussion.comments') AS comment
+             WHERE comment.type = 'object')
+           + (SELECT COUNT(*)
+             FROM JSON_EACH(c.patch, '$.revisions') AS revision,
+                  JSON_EACH(revision.value, '$.reviews') AS review,
+                  JSON_EACH(review.value, '$.comments.comments') AS comment
+             WHERE comment.type = 'object')",
+        author: "c.patch->>'$.author.id'",
+        reviewers: Some(
+            "SELECT 'did:key:' || review.key
+             FROM JSON_EACH(c.patch, '$.revisions') AS revision,
+                  JSON_EACH(revision.value, '$.reviews') AS review
+             WHERE review.type = 'object'",
+        ),
+    };
+}
+
 impl Sqlite {
-    /// Issues for `rid`, newest first, optionally filtered by state. Single
-    /// home of the issue-listing SQL; `list`/`list_by_status` differ only in
-    /// the status predicate. The sort key is the root comment's timestamp
-    /// (the comment without a `replyTo`) — the issue's creation time — not
-    /// the minimum across replies, whose author-supplied clocks could
-    /// otherwise sink an issue below its real position.
-    fn issues_by(
+    /// COBs of one kind for `rid`, optionally with the given status, narrowed
+    /// and ordered by `filter`, starting right after `after` if set. Single
+    /// home of the issue and patch listing SQL. Rows that fail to parse are
+    /// skipped.
+    ///
+    /// Rows are ordered by their sort key and then id, which together are
+    /// unique, so a cursor made of both marks a position that survives
+    /// inserts and cache rebuilds.
+    fn cobs_by<T: serde::de::DeserializeOwned>(
         &self,
+        query: &CobQuery,
         rid: identity::RepoId,
-        status: Option<IssueStatus>,
-    ) -> Result<impl Iterator<Item = (IssueId, Issue)>, ListIssuesError> {
-        let filter = if status.is_some() {
-            "AND issue->>'$.state.status' = ?2"
-        } else {
-            ""
+        status: Option<&str>,
+        filter: &ListFilter,
+        after: Option<&Cursor>,
+    ) -> Result<impl Iterator<Item = (ObjectId, T, Cursor)>, sql::Error> {
+        let CobQuery {
+            table,
+            column,
+            created,
+            last_activity,
+            comment_count,
+            author,
+            reviewers,
+        } = query;
+        let mut params = vec![sql::Value::String(rid.to_string())];
+        let mut clauses = String::new();
+
+        if let Some(status) = status {
+            let status = placeholders(vec![status.into()], &mut params);
+            clauses.push_str(&format!(" AND c.{column}->>'$.state.status' = {status}"));
+        }
+        if let Some(labels) = &filter.labels {
+            let (labels, all) = match labels {
+                LabelFilter::Any(labels) => (labels, false),
+                LabelFilter::All(labels) => (labels, true),
+            };
+            let distinct = labels.iter().collect::<BTreeSet<_>>();
+            let list = placeholders(
+                distinct.iter().map(|l| l.as_str().into()).collect(),
+                &mut params,
+            );
+            let matching = format!(
+                "(SELECT COUNT(DISTINCT label.value)
+                  FROM JSON_EACH(c.{column}, '$.labels') AS label
+                  WHERE label.value IN ({list}))"
+            );
+            if all {
+                clauses.push_str(&format!(" AND {matching} = {}", distinct.len()));
+            } else {
+                clauses.push_str(&format!(" AND {matching} > 0"));
+            }
+        }
+        if let Some(did) = &filter.author {
+            let did = placeholders(vec![did.to_string().into()], &mut params);
+            clauses.push_str(&format!(" AND {author} = {did}"));
+        }
+        if let Some(did) = &filter.assignee {
+            clauses.push_str(&assignee_clause(column, did, &mut params));
+        }
+        if let Some(did) = &filter.reviewer {
+            match reviewers {
+                Some(reviewers) => {
+                    let did = placeholders(vec![did.to_string().into()], &mut params);
+                    clauses.push_str(&format!(" AND {did} IN ({reviewers})"));
+                }
+                // Nothing without reviews can match a reviewer.
+                None => clauses.push_str(" AND FALSE"),
+            }
+        }
+        if let Some(since) = filter.updated_since {
+            let since = placeholders(vec![(since as i64).into()], &mut params);
+            clauses.push_str(&format!(" AND last_activity >= {since}"));
+        }
+
+        let sort = match filter.sort {
+            Sort::Created => "created",
+            Sort::LastActivity => "last_activity",
+            Sort::CommentCount => "comment_count",
         };
+        let order = filter.order.as_sql();
+        if let Some(after) = after {
+            let op = match filter.order {
+                Order::Asc => ">",
+                Order::Desc => "<",
+            };
+            let key = placeholders(vec![after.key.into()], &mut params);
+            let id = placeholders(vec![after.id.as_str().into()], &mut params);
+            clauses.push_str(&format!(
+                " AND ({sort} {op} {key} OR ({sort} = {key} AND id {op} {id}))"
+            ));
+        }
         let mut stmt = self.db.prepare(format!(
-            "SELECT id, issue, (
-                 SELECT MIN(JSON_EXTRACT(comment.value, '$.edits[0].timestamp'))
-                 FROM JSON_EACH(JSON_EXTRACT(i.issue, '$.thread.comments')) AS comment
-                 WHERE JSON_EXTRACT(comment.value, '$.replyTo') IS NULL
-             ) AS created_timestamp
-             FROM issues AS i
+            "SELECT id, {column} AS cob,
+                 COALESCE({created}, 0) AS created,
+                 COALESCE({last_activity}, 0) AS last_activity,
+                 {comment_count} AS comment_count
+             FROM {table} AS c
              WHERE repo = ?1
-             {filter}
-             ORDER BY created_timestamp DESC, id DESC;
+             {clauses}
+             ORDER BY {sort} {order}, id {order};
              "
         ))?;
-        stmt.bind((1, &rid))?;
-        if let Some(status) = status {
-            stmt.bind((2, status.as_str()))?;
+        for (i, value) in params.into_iter().enumerate() {
+            stmt.bind((i + 1, value))?;
         }
-        Ok(stmt.into_iter().filter_map(|row| {
+
+        Ok(stmt.into_iter().filter_map(move |row| {
             let row = row.ok()?;
-            let id = IssueId::from_str(row.read::<&str, _>("id")).ok()?;
-            let issue = serde_json::from_str::<Issue>(row.read::<&str, _>("issue")).ok()?;
-            Some((id, issue))
+            let id = ObjectId::from_str(row.read::<&str, _>("id")).ok()?;
+            let cob = serde_json::from_str::<T>(row.read::<&str, _>("cob")).ok()?;
+            let position = Cursor {
+                key: row.read::<i64, _>(sort),
+                id: id.to_string(),
+            };
+            Some((id, cob, position))
         }))
     }
 }
 
+impl Sqlite {
+    /// Label and assignee counts for one COB kind, most common first, with
+    /// ties broken by value so the order is stable.
+    fn facet_counts(
+        &self,
+        query: &CobQuery,
+        rid: identity::RepoId,
+        status: Option<&str>,
+    ) -> Result<FacetCounts, anyhow::Error> {
+        let CobQuery { table, column, .. } = query;
+        let filter = if status.is_some() {
+            format!("AND c.{column}->>'$.state.status' = ?2")
+        } else {
+            String::new()
+        };
+        let counts = |path: &str| -> Result<Vec<(String, usize)>, anyhow::Error> {
+            let mut stmt = self.db.prepare(format!(
+                "SELECT facet.value AS value, COUNT(DISTINCT c.id) AS count
+                 FROM {table} AS c, JSON_EACH(c.{column}, '{path}') AS facet
+                 WHERE repo = ?1
+                 {filter}
+                 GROUP BY facet.value
+                 ORDER BY count DESC, value"
+            ))?;
+            stmt.bind((1, &rid))?;
+            if let Some(status) = status {
+                stmt.bind((2, status))?;
+            }
+
+            stmt.into_iter()
+                .map(|row| {
+                    let row = row?;
+                    Ok((
+                        row.try_read::<&str, _>("value")?.to_owned(),
+                        row.try_read::<i64, _>("count")? as usize,
+                    ))
+                })
+                .collect()
+        };
+
+        // Like the listings, a value that doesn't parse is skipped rather
+        // than failing the whole sidebar.
+        Ok(FacetCounts {
+            labels: counts("$.labels")?
+                .into_iter()
+                .filter_map(|(label, count)| match cob::Label::new(&label) {
+                    Ok(label) => Some((label, count)),
+                    Err(e) => {
+                        log::warn!("Skipping invalid label `{label}` in {rid}: {e}");
+                        None
+                    }
+                })
+                .collect(),
+            assignees: counts("$.assignees")?
+                .into_iter()
+                .filter_map(|(did, count)| match parse_did(&did) {
+                    Ok(did) => Some((did, count)),
+                    Err(e) => {
+                        log::warn!("Skipping invalid assignee `{did}` in {rid}: {e}");
+                        None
+                    }
+                })
+                .collect(),
+        })
+    }
+}
+
 impl IssueStorage for Sqlite {
     fn list(
         &self,
         rid: identity::RepoId,
     ) -> Result<impl Iterator<Item = (IssueId, Issue)>, ListIssuesError> {
-        self.issues_by(rid, None)
+        Ok(self
+            .list_filtered(rid, None, &ListFilter::default(), None)?
+            .map(|(id, issue, _)| (id, issue)))
     }
 
     fn list_by_status(
@@ -169,124 +452,240 @@ impl IssueStorage for Sqlite {
         rid: identity::RepoId,
         status: IssueStatus,
     ) -> Result<impl Iterator<Item = (IssueId, Issue)>, ListIssuesError> {
-        self.issues_by(rid, Some(status))
+        Ok(self
+            .list_filtered(rid, Some(status), &ListFilter::default(), None)?
+            .map(|(id, issue, _)| (id, issue)))
     }
-}
 
-impl InboxStorage for Sqlite {
-    fn counts_by_repo(
+    fn list_filtered(
         &self,
-    ) -> Result<
-        impl Iterator<Item = Result<notification::CountByRepo, notification::ListNotificationsError>>,
-        notification::ListNotificationsError,
-    > {
-        let stmt = self.db.prepare(
-            "SELECT COUNT(DISTINCT substr(ref, 66)) count, ref, repo
-                 FROM `repository-notifications`
-                 WHERE new NOT NULL AND (ref LIKE '%cobs/xyz.radicle.patch%' OR ref LIKE '%cobs/xyz.radicle.issue%')
-                 GROUP BY repo",
-        )?;
-
-        Ok(stmt.into_iter().map(|row| {
-            let row = row?;
-            let count = row.try_read::<i64, _>("count")? as usize;
-            let repo = row.try_read::<identity::RepoId, _>("repo")?;
+        rid: identity::RepoId,
+        status: Option<IssueStatus>,
+        filter: &ListFilter,
+        after: Option<&Cursor>,
+    ) -> Result<impl Iterator<Item = (IssueId, Issue, Cursor)>, ListIssuesError> {
+        let status = status.map(|s| s.as_str());
 
-            Ok((repo, count))
-        }))
+        Ok(self.cobs_by::<Issue>(&CobQuery::ISSUES, rid, status, filter, after)?)
     }
 
-    fn notification_count(&self) -> Result<usize, notification::ListNotificationsError> {
-        let stmt = self.db.prepare(
-            "SELECT COUNT(DISTINCT substr(ref, 66)) as count
-             FROM `repository-notifications`
-             WHERE new NOT NULL AND (ref LIKE '%cobs/xyz.radicle.patch%' OR ref LIKE '%cobs/xyz.radicle.issue%')",
+    fn counts(&self, rid: identity::RepoId) -> Result<IssueCounts, IssueCountsError> {
+        let mut stmt = self.db.prepare(
+            "SELECT issue->>'$.state.status' AS status, COUNT(*) AS count
+             FROM issues
+             WHERE repo = ?1
+             GROUP BY status",
         )?;
+        stmt.bind((1, &rid))?;
 
-        match stmt.into_iter().next() {
-            Some(Ok(row)) => Ok(row.try_read::<i64, _>("count")? as usize),
-            _ => Ok(0),
-        }
+        stmt.into_iter()
+            .try_fold(IssueCounts::default(), |mut counts, row| {
+                let row = row?;
+                let count = row.try_read::<i64, _>("count")? as usize;
+                match row.try_read::<&str, _>("status")? {
+                    "open" => counts.open += count,
+                    "closed" => counts.closed += count,
+                    other => {
+                        return Err(IssueCountsError::Unknown(anyhow::anyhow!(
+                            "unknown issue status `{other}`"
+                        )));
+                    }
+                }
+                Ok(counts)
+            })
     }
 
-    fn repo_group(
+    fn facets(
         &self,
-        params: notification::RepoGroupParams,
-    ) -> Result<
-        Vec<(identity::RepoId, notification::RepoGroup)>,
-        notification::ListNotificationsError,
-    > {
-        let repos_clause = match &params.repos {
-            Some(repos) if !repos.is_empty() => {
-                let placeholders: Vec<String> =
-                    (1..=repos.len()).map(|i| format!("?{}", i)).collect();
-                format!("AND repo IN ({})", placeholders.join(","))
-            }
-            _ => String::from(""),
-        };
+        rid: identity::RepoId,
+        status: Option<IssueStatus>,
+    ) -> Result<FacetCounts, IssueCountsError> {
+        self.facet_counts(&CobQuery::ISSUES, rid, status.map(|s| s.as_str()))
+            .map_err(IssueCountsError::Unknown)
+    }
+}
 
-        let query = format!(
-            "SELECT repo, ref, substr(ref, 66) ref_without_namespace,
-                json_group_array(
-                    json_object(
-                        'row_id', rowid,
-                        'timestamp', timestamp,
-                        'remote', substr(ref, 17, 48),
-                        'old', old,
-                        'new', new
-                    )
-                ) as value,
-                MAX(timestamp) AS latest_timestamp
-            FROM 'repository-notifications'
-            WHERE new NOT NULL
-              AND (ref LIKE '%cobs/xyz.radicle.patch%'
-                   OR ref LIKE '%cobs/xyz.radicle.issue%')
-            {}
-            GROUP BY repo, ref_without_namespace
-            ORDER BY latest_timestamp DESC",
-            repos_clause
-        );
+/// Parses an assignee, which patches store as a bare key and issues as a DID.
+fn parse_did(s: &str) -> Result<identity::Did, anyhow::Error> {
+    match identity::Did::from_str(s) {
+        Ok(did) => Ok(did),
+        Err(_) => Ok(radicle::crypto::PublicKey::from_str(s)?.into()),
+    }
+}
 
-        let mut stmt = self.db.prepare(&query)?;
+/// Matches the COBs in `column` assigned to `did`. Patches store assignees as
+/// bare keys and issues as DIDs, so either form matches.
+fn assignee_clause(column: &str, did: &identity::Did, params: &mut Vec<sql::Value>) -> String {
+    let did = placeholders(
+        vec![did.to_string().into(), did.as_key().to_string().into()],
+        params,
+    );
 
-        if let Some(repos) = &params.repos
-            && !repos.is_empty()
-        {
-            for (i, repo) in repos.iter().enumerate() {
-                stmt.bind((i + 1, repo))?;
-            }
-        }
+    format!(
+        " AND EXISTS (SELECT 1 FROM JSON_EACH(c.{column}, '$.assignees') AS assignee
+          WHERE assignee.value IN ({did}))"
+    )
+}
 
-        let mut result: Vec<(identity::RepoId, notification::RepoGroup)> = Vec::new();
-        let mut current_repo: Option<identity::RepoId> = None;
-        let mut current_group: notification::RepoGroup = Vec::new();
-
-        for row_result in stmt.into_iter() {
-            let row = row_result?;
-            let repo_id = row.try_read::<identity::RepoId, _>("repo")?;
-            let refstr = row.try_read::<&str, _>("ref")?;
-            let value = row.try_read::<&str, _>("value")?;
-            let items = serde_json::from_str::<Vec<notification::NotificationRow>>(value)?;
-            let (_, reference) = git::parse_ref::<String>(refstr)?;
-
-            if let Some(current) = current_repo {
-                if current != repo_id {
-                    result.push((current, std::mem::take(&mut current_group)));
-                    current_repo = Some(repo_id);
-                }
-            } else {
-                current_repo = Some(repo_id);
+/// Appends `values` to `params`, returning their placeholders as a list.
+pub(crate) fn placeholders(values: Vec<sql::Value>, params: &mut Vec<sql::Value>) -> String {
+    let start = params.len() + 1;
+    let list = (start..start + values.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    params.extend(values);
+
+    list
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::collections::BTreeSet;
+    use std::str::FromStr as _;
+
+    use radicle::cob::cache::COBS_DB_FILE;
+    use radicle::identity::{Did, RepoId};
+    use sqlite as sql;
+
+    use super::{Sqlite, assignee_clause, digest};
+    use crate::cobs::CobOptions;
+    use crate::cobs::issue::NewIssue;
+    use crate::cobs::query::{ListFilter, Order};
+    use crate::domain::issue::traits::IssueStorage;
+    use crate::traits::Profile as _;
+    use crate::traits::issue::IssuesMut;
+    use crate::{AppState, test};
+
+    const NID: &str = "z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi";
+    const OID: &str = "e7a8b5e4ab6c2a4e1b7b3a1ec9b8f5a6d2c1b0a9";
+
+    fn open_issue(state: &AppState, rid: RepoId, title: &str) {
+        state
+            .create_issue(
+                rid,
+                NewIssue {
+                    title: title.to_owned(),
+                    description: String::new(),
+                    labels: vec![],
+                    assignees: vec![],
+                    embeds: vec![],
+                },
+                serde_json::from_str::<CobOptions>(r#"{ "announce": false }"#).unwrap(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn pages_survive_inserts() {
+        for order in [Order::Asc, Order::Desc] {
+            let tmp = tempfile::tempdir().unwrap();
+            let (state, rid, _) = test::repo(tmp.path());
+            for title in ["One", "Two", "Three", "Four"] {
+                open_issue(&state, rid, title);
             }
+            let cobs = Sqlite::reader(state.profile().cobs().join(COBS_DB_FILE)).unwrap();
+            let filter = ListFilter {
+                order,
+                ..ListFilter::default()
+            };
+            let existing = cobs
+                .list_filtered(rid, None, &filter, None)
+                .unwrap()
+                .map(|(id, _, _)| id)
+                .collect::<BTreeSet<_>>();
+
+            let first = cobs
+                .list_filtered(rid, None, &filter, None)
+                .unwrap()
+                .take(2)
+                .collect::<Vec<_>>();
+            open_issue(&state, rid, "Inserted");
+            let (_, _, cursor) = first.last().unwrap();
+            let second = cobs
+                .list_filtered(rid, None, &filter, Some(cursor))
+                .unwrap()
+                .collect::<Vec<_>>();
 
-            current_group.push((reference.to_owned(), items));
+            let listed = first
+                .iter()
+                .chain(&second)
+                .map(|(id, _, _)| *id)
+                .collect::<Vec<_>>();
+            let unique = listed.iter().copied().collect::<BTreeSet<_>>();
+            assert_eq!(unique.len(), listed.len(), "{order:?}: duplicates");
+            assert!(existing.is_subset(&unique), "{order:?}: gaps");
+            // The inserted issue sorts before the cursor or after it, but is
+            // never listed twice.
+            assert!(listed.len() - existing.len() <= 1);
         }
+    }
+
+    #[test]
+    fn assignee_as_key_or_did() {
+        let did =
+            Did::from_str("did:key:z6MknSLrJoTcukLrE435hVNQT4JUhbvWLX4kUzqkEStBU8Vi").unwrap();
+        let db = sql::Connection::open(":memory:").unwrap();
+        db.execute(format!(
+            "CREATE TABLE cobs (cob TEXT NOT NULL);
+             INSERT INTO cobs VALUES ('{{\"assignees\": [\"{}\"]}}');
+             INSERT INTO cobs VALUES ('{{\"assignees\": [\"{did}\"]}}');
+             INSERT INTO cobs VALUES ('{{\"assignees\": []}}');",
+            did.as_key()
+        ))
+        .unwrap();
 
-        if let Some(repo) = current_repo
-            && !current_group.is_empty()
-        {
-            result.push((repo, current_group));
+        let mut params = Vec::new();
+        let clause = assignee_clause("cob", &did, &mut params);
+        let mut stmt = db
+            .prepare(format!(
+                "SELECT COUNT(*) AS n FROM cobs c WHERE TRUE {clause}"
+            ))
+            .unwrap();
+        for (i, value) in params.into_iter().enumerate() {
+            stmt.bind((i + 1, value)).unwrap();
         }
+        let count = stmt
+            .into_iter()
+            .next()
+            .unwrap()
+            .unwrap()
+            .read::<i64, _>("n");
+
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn facets_skip_invalid_values() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cobs.db");
+        let rid = RepoId::from_str("rad:z3gqcJUoA1n9HaHKufZs5FCSGazv5").unwrap();
+        let db = sql::Connection::open(&path).unwrap();
+        db.execute(format!(
+            "CREATE TABLE issues (id TEXT NOT NULL, repo TEXT NOT NULL, issue TEXT NOT NULL);
+             INSERT INTO issues VALUES ('{OID}', '{rid}', '{{
+                 \"labels\": [\"bug\", \"not a label\"],
+                 \"assignees\": [\"{NID}\", \"nobody\"]
+             }}');"
+        ))
+        .unwrap();
+
+        let facets = Sqlite::reader(&path).unwrap().facets(rid, None).unwrap();
+        assert_eq!(
+            facets
+                .labels
+                .iter()
+                .map(|(label, count)| (label.as_str(), *count))
+                .collect::<Vec<_>>(),
+            vec![("bug", 1)]
+        );
+        assert_eq!(facets.assignees.len(), 1);
+    }
 
-        Ok(result)
+    #[test]
+    fn digest_is_fnv1a() {
+        assert_eq!(digest(""), 0xcbf29ce484222325);
+        assert_eq!(digest("a"), 0xaf63dc4c8601ec8c);
     }
 }
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use radicle::node;
+use radicle::node::Alias;
+
+/// Whether we fetch from a peer or repository, or refuse to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "policy/")]
+pub enum Policy {
+    Allow,
+    Block,
+}
+
+impl From<node::policy::Policy> for Policy {
+    fn from(value: node::policy::Policy) -> Self {
+        match value {
+            node::policy::Policy::Allow => Self::Allow,
+            node::policy::Policy::Block => Self::Block,
+        }
+    }
+}
+
+/// A peer we follow or block.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "policy/")]
+pub struct FollowPolicy {
+    #[ts(as = "String")]
+    pub nid: node::NodeId,
+    /// The alias given when following, or else the one the peer announced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub alias: Option<Alias>,
+    pub policy: Policy,
+}
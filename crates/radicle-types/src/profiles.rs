@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use radicle::Storage;
+use radicle::crypto::ssh::Keystore;
+use radicle::node::{Alias, NodeId};
+use radicle::profile::Home;
+
+use crate::error::Error;
+
+/// A Radicle home the app knows about.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "profiles/")]
+pub struct ProfileEntry {
+    #[ts(as = "String")]
+    pub home: PathBuf,
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub nid: Option<NodeId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub alias: Option<Alias>,
+    /// Why the profile can't be loaded, e.g. because its home was moved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+/// The Radicle homes registered with the app, and which of them is in use.
+/// Kept in a JSON file of the app's own, which is rewritten on every change.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Registry {
+    #[serde(skip)]
+    path: PathBuf,
+    active: Option<PathBuf>,
+    homes: Vec<PathBuf>,
+}
+
+impl Registry {
+    /// Open the registry at `path`. A missing file is an empty registry.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let registry = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self { path, ..registry })
+    }
+
+    /// The home of the profile in use, if one was ever activated.
+    pub fn active(&self) -> Option<&Path> {
+        self.active.as_deref()
+    }
+
+    /// Register `home`, unless it already is.
+    pub fn add(&mut self, home: PathBuf) -> Result<(), Error> {
+        if !self.homes.contains(&home) {
+            self.homes.push(home);
+            self.save()?;
+        }
+
+        Ok(())
+    }
+
+    /// Register `home` if needed, and make it the one in use.
+    pub fn activate(&mut self, home: PathBuf) -> Result<(), Error> {
+        if !self.homes.contains(&home) {
+            self.homes.push(home.clone());
+        }
+        self.active = Some(home);
+
+        self.save()
+    }
+
+    /// Forget about `home`. Its files are left alone.
+    pub fn remove(&mut self, home: &Path) -> Result<(), Error> {
+        if self.active() == Some(home) {
+            return Err(Error::ActiveProfile);
+        }
+        self.homes.retain(|h| h != home);
+
+        self.save()
+    }
+
+    /// Every registered profile, in the order they were added.
+    pub fn entries(&self) -> Vec<ProfileEntry> {
+        self.homes
+            .iter()
+            .map(|home| {
+                let active = self.active() == Some(home.as_path());
+                match load(home) {
+                    Ok(profile) => ProfileEntry {
+                        home: home.clone(),
+                        active,
+                        nid: Some(profile.public_key),
+                        alias: Some(profile.config.node.alias),
+                        error: None,
+                    },
+                    Err(e) => ProfileEntry {
+                        home: home.clone(),
+                        active,
+                        nid: None,
+                        alias: None,
+                        error: Some(e.to_string()),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        // Renamed over the old file, so it's never left half written.
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp, &self.path)?;
+
+        Ok(())
+    }
+}
+
+/// Load the profile at `home`, the way `radicle::Profile::load` does with
+/// the home from the environment.
+pub fn load(home: &Path) -> Result<radicle::Profile, Error> {
+    // `Home::new` creates the directory, which is not wanted for a home that
+    // was moved or deleted.
+    if !home.exists() {
+        return Err(radicle::profile::Error::NotFound(home.to_path_buf()).into());
+    }
+    let home = Home::new(home)?;
+    let keystore = Keystore::new(&home.keys());
+    let public_key = keystore
+        .public_key()?
+        .ok_or_else(|| radicle::profile::Error::NotFound(home.path().to_path_buf()))?;
+    let config =
+        serde_json::from_str::<radicle::profile::Config>(&fs::read_to_string(home.config())?)
+            .map_err(|e| Error::InvalidConfig(e.to_string()))?;
+    let storage = Storage::open(
+        home.storage(),
+        radicle::git::UserInfo {
+            alias: config.node.alias.clone(),
+            key: public_key,
+        },
+    )?;
+
+    Ok(radicle::Profile {
+        home,
+        storage,
+        keystore,
+        public_key,
+        config,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use crate::error::Error;
+    use crate::profiles::Registry;
+    use crate::test;
+
+    #[test]
+    fn registry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let work = tmp.path().join("work");
+        let profile = test::profile(&work, [0xff; 32]);
+        std::fs::write(
+            profile.home().config(),
+            serde_json::to_string(&profile.config).unwrap(),
+        )
+        .unwrap();
+        let moved = tmp.path().join("moved");
+        let path = tmp.path().join("profiles.json");
+
+        let mut registry = Registry::open(&path).unwrap();
+        assert!(registry.entries().is_empty());
+        registry.activate(work.clone()).unwrap();
+        registry.add(moved.clone()).unwrap();
+        registry.add(work.clone()).unwrap();
+
+        let mut registry = Registry::open(&path).unwrap();
+        assert_eq!(registry.active(), Some(work.as_path()));
+        let entries = registry.entries();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].active);
+        assert_eq!(entries[0].nid, Some(profile.public_key));
+        assert!(!entries[1].active);
+        assert!(entries[1].error.is_some());
+
+        assert!(matches!(registry.remove(&work), Err(Error::ActiveProfile)));
+        registry.remove(&moved).unwrap();
+        assert_eq!(Registry::open(&path).unwrap().entries().len(), 1);
+    }
+}
@@ -1,10 +1,12 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 use radicle_surf as surf;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use radicle::node::Alias;
+use radicle::node::{Alias, AliasStore};
 use radicle::{git, identity, issue, node, patch};
 
 use crate::cobs::Author;
@@ -62,7 +64,7 @@ pub struct Readme {
     pub path: String,
 }
 
-#[derive(Default, Serialize, TS)]
+#[derive(Default, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase", tag = "type")]
 #[ts(export)]
 #[ts(export_to = "repo/")]
@@ -78,6 +80,17 @@ pub enum Visibility {
     },
 }
 
+impl Visibility {
+    pub fn new(visibility: &identity::Visibility, aliases: &impl AliasStore) -> Self {
+        match visibility {
+            identity::Visibility::Public => Self::Public,
+            identity::Visibility::Private { allow } => Self::Private {
+                allow: allow.iter().map(|did| Author::new(did, aliases)).collect(),
+            },
+        }
+    }
+}
+
 impl From<Visibility> for identity::Visibility {
     fn from(value: Visibility) -> Self {
         match value {
@@ -93,6 +106,228 @@ impl From<Visibility> for identity::Visibility {
     }
 }
 
+/// Which peers a repository is fetched from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub enum Scope {
+    /// Only the delegates and the peers we follow.
+    Followed,
+    /// Everyone.
+    #[default]
+    All,
+}
+
+impl From<Scope> for node::policy::Scope {
+    fn from(value: Scope) -> Self {
+        match value {
+            Scope::Followed => Self::Followed,
+            Scope::All => Self::All,
+        }
+    }
+}
+
+impl From<node::policy::Scope> for Scope {
+    fn from(value: node::policy::Scope) -> Self {
+        match value {
+            node::policy::Scope::Followed => Self::Followed,
+            node::policy::Scope::All => Self::All,
+        }
+    }
+}
+
+/// A repository we seed.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct SeedPolicy {
+    #[ts(as = "String")]
+    pub rid: identity::RepoId,
+    /// The project name, once the repository is in storage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub name: Option<String>,
+    pub scope: Scope,
+    /// Whether the repository has been fetched into storage yet.
+    pub replicated: bool,
+}
+
+/// A repository we seed but don't have in storage yet, with what is known
+/// about why.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct PendingRepo {
+    #[ts(as = "String")]
+    pub rid: identity::RepoId,
+    /// Seeds that announced the repository, according to the routing table.
+    pub providers: Vec<Provider>,
+    /// The last time the app tried to fetch it, if it did since it started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub last_attempt: Option<FetchAttempt>,
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct Provider {
+    #[ts(as = "String")]
+    pub nid: node::NodeId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub alias: Option<Alias>,
+    /// Whether our node has a session with it.
+    pub connected: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct FetchAttempt {
+    /// Unix time in milliseconds.
+    #[ts(type = "number")]
+    pub timestamp: u64,
+    pub outcome: FetchOutcome,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(tag = "status", rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub enum FetchOutcome {
+    #[serde(rename_all = "camelCase")]
+    Fetched {
+        #[ts(as = "String")]
+        nid: node::NodeId,
+    },
+    /// Every provider failed, the reason is the last one given.
+    #[serde(rename_all = "camelCase")]
+    Failed { reason: String },
+    /// No seed is known to have the repository.
+    NoProviders,
+}
+
+/// The outcome of the last fetch attempt of every repository, kept in memory
+/// for as long as the app runs. Cloning shares the log.
+#[derive(Clone, Default)]
+pub struct FetchLog(Arc<Mutex<HashMap<identity::RepoId, FetchAttempt>>>);
+
+impl FetchLog {
+    pub fn get(&self, rid: &identity::RepoId) -> Option<FetchAttempt> {
+        self.lock().get(rid).cloned()
+    }
+
+    pub fn record(&self, rid: identity::RepoId, outcome: FetchOutcome) -> FetchAttempt {
+        let attempt = FetchAttempt {
+            timestamp: radicle_localtime::LocalTime::now().as_millis(),
+            outcome,
+        };
+        self.lock().insert(rid, attempt.clone());
+
+        attempt
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<identity::RepoId, FetchAttempt>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// How much disk space the repositories in storage take.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct DiskUsage {
+    /// In bytes.
+    #[ts(type = "number")]
+    pub total: u64,
+    /// Largest first.
+    pub repos: Vec<RepoUsage>,
+}
+
+/// Sizes are in bytes.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct RepoUsage {
+    #[ts(as = "String")]
+    pub rid: identity::RepoId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub name: Option<String>,
+    /// Everything in the repository's directory.
+    #[ts(type = "number")]
+    pub total: u64,
+    #[ts(type = "number")]
+    pub loose_objects: u64,
+    #[ts(type = "number")]
+    pub packs: u64,
+    pub remotes: Vec<RemoteUsage>,
+    /// Why the repository couldn't be read. Only `total` is known then.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub error: Option<String>,
+}
+
+/// A remote's namespace in a repository. Sizes are in bytes.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct RemoteUsage {
+    #[ts(as = "String")]
+    pub nid: node::NodeId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub alias: Option<Alias>,
+    /// How many refs the remote has in its namespace.
+    pub refs: usize,
+    /// Objects reachable from the remote's refs. Objects are shared between
+    /// remotes, so these add up to more than the repository takes. Only
+    /// counted when a single repository is asked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(type = "number", optional)]
+    pub size: Option<u64>,
+    /// Objects reachable from no other remote's refs, which is about what
+    /// dropping the remote would free once the repository is pruned. Only
+    /// counted along with `size`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(type = "number", optional)]
+    pub unique: Option<u64>,
+}
+
+/// A git working copy to turn into a Radicle repository.
+#[derive(Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct InitRepo {
+    #[ts(as = "String")]
+    pub path: PathBuf,
+    pub name: String,
+    pub description: String,
+    pub default_branch: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub visibility: Visibility,
+    /// Seed the new repository, and announce it if it's public.
+    #[serde(default)]
+    #[ts(optional)]
+    pub seed: bool,
+    /// The peers to fetch the repository from once it's seeded. Defaults to
+    /// the scope of the profile's default seeding policy.
+    #[serde(default)]
+    #[ts(optional)]
+    pub scope: Option<Scope>,
+}
+
 #[derive(Serialize, TS)]
 #[ts(export)]
 #[ts(export_to = "repo/")]
@@ -214,6 +449,8 @@ pub struct Remote {
     #[ts(as = "Option<String>", optional)]
     pub alias: Option<Alias>,
     pub delegate: bool,
+    /// Whether we follow this peer.
+    pub followed: bool,
     #[ts(as = "BTreeMap<String, String>")]
     pub branches: BTreeMap<String, git::Oid>,
     pub tags: BTreeMap<String, Tag>,
@@ -229,6 +466,70 @@ pub struct Canonical {
     pub tags: BTreeMap<String, Tag>,
 }
 
+/// A canonical-refs rule from the identity document, with the quorum of every
+/// ref it covers.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct CanonicalRule {
+    /// Ref pattern the rule applies to, e.g. `refs/tags/*`.
+    pub pattern: String,
+    /// The peers whose refs count as votes.
+    pub allowed: Vec<Author>,
+    pub threshold: usize,
+    pub refs: Vec<RefQuorum>,
+}
+
+/// How the allowed peers vote on a single canonical ref.
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct RefQuorum {
+    /// Fully qualified ref name, e.g. `refs/heads/main`.
+    pub name: String,
+    /// Where the canonical ref currently points, if it exists.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub canonical: Option<git::Oid>,
+    pub votes: Vec<Vote>,
+    pub quorum: Quorum,
+}
+
+#[derive(Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub struct Vote {
+    pub peer: Author,
+    /// The commit, or tag object, the peer has the ref at. Absent if the
+    /// peer doesn't have the ref.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(as = "Option<String>", optional)]
+    pub oid: Option<git::Oid>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "camelCase", tag = "status")]
+#[ts(export)]
+#[ts(export_to = "repo/")]
+pub enum Quorum {
+    /// Enough votes agree on `oid`.
+    Reached {
+        #[ts(as = "String")]
+        oid: git::Oid,
+    },
+    /// No commit has enough votes.
+    NotReached,
+    /// Several commits have enough votes, and none of them includes the
+    /// others.
+    Diverged {
+        #[ts(as = "Vec<String>")]
+        candidates: Vec<git::Oid>,
+    },
+}
+
 #[derive(Serialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
@@ -4,9 +4,12 @@ use radicle::Storage;
 use radicle::cob::migrate;
 use radicle::crypto::ssh::Keystore;
 use radicle::crypto::{Seed, Signer, SigningKey};
+use radicle::identity::{RepoId, Visibility};
 use radicle::node::{Features, Timestamp, UserAgent};
 use radicle::profile::Home;
-use radicle::{node, profile};
+use radicle::{git, node, profile};
+
+use crate::AppState;
 
 pub const TIMESTAMP: u64 = 1671125284;
 
@@ -57,3 +60,23 @@ pub fn profile(home: &Path, seed: [u8; 32]) -> radicle::Profile {
         config,
     }
 }
+
+/// A profile under `tmp` seeding a freshly initialized project, together with
+/// the project's canonical head.
+pub fn repo(tmp: &Path) -> (AppState, RepoId, git::Oid) {
+    let profile = profile(&tmp.join("home"), [0xff; 32]);
+    let signer = profile.signer().unwrap();
+    let (working, head) = radicle::test::fixtures::repository(tmp.join("working"));
+    let (rid, _, _) = radicle::rad::init(
+        &working,
+        "acme".try_into().unwrap(),
+        "Acme's repository",
+        git::fmt::RefString::try_from("master").unwrap(),
+        Visibility::default(),
+        &signer,
+        &profile.storage,
+    )
+    .unwrap();
+
+    (AppState::new(profile), rid, head.into())
+}
@@ -1,11 +1,21 @@
+use std::{fs, io};
+
+use radicle::node::device::BoxedDevice;
 use radicle::node::{AliasStore, NodeId};
 
-use crate::config::Config;
+use crate::config::{Config, ConfigEdit, ConfigUpdate, NodeSetting};
+use crate::error::Error;
 
 pub mod cobs;
+pub mod diagnostics;
+pub mod identity;
+pub mod inbox;
 pub mod issue;
 pub mod job;
+pub mod keys;
+pub mod node;
 pub mod patch;
+pub mod policy;
 pub mod repo;
 pub mod thread;
 
@@ -21,6 +31,9 @@ pub trait Profile {
             seeding_policy: p.config.node.seeding_policy,
             public_explorer: p.config.public_explorer.clone(),
             preferred_seeds: p.config.preferred_seeds.clone(),
+            listen: p.config.node.listen.clone(),
+            connect: p.config.node.connect.iter().cloned().collect(),
+            external_addresses: p.config.node.external_addresses.clone(),
         }
     }
 
@@ -30,6 +43,69 @@ pub trait Profile {
 
         aliases.alias(&nid)
     }
+
+    /// The signer for writes. Goes through the ssh-agent, unless overridden.
+    fn signer(&self) -> Result<BoxedDevice, Error> {
+        Ok(self.profile().signer()?)
+    }
+}
+
+pub trait ProfileMut: Profile {
+    /// Replace the profile, e.g. after its configuration changed on disk.
+    /// Run `f` on the profile with every other reader and writer locked out,
+    /// so a change made from what `f` reads is never lost to a concurrent one.
+    fn modify_profile<T>(&self, f: impl FnOnce(&mut radicle::Profile) -> T) -> T;
+
+    fn set_profile(&self, profile: radicle::Profile) {
+        self.modify_profile(|p| *p = profile)
+    }
+
+    /// Apply `edit` to the profile's `config.json` and reload the profile.
+    fn update_config(&self, edit: ConfigEdit) -> Result<ConfigUpdate, Error> {
+        let restart = self.modify_profile(|profile| -> Result<_, Error> {
+            let path = profile.home().config();
+
+            // The edits are made to the file as it is, so that settings this
+            // app doesn't know about survive.
+            let mut raw = match fs::read_to_string(&path) {
+                Ok(json) => serde_json::from_str(&json)?,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    serde_json::to_value(&profile.config)?
+                }
+                Err(e) => return Err(e.into()),
+            };
+            edit.apply(&mut raw)?;
+            let config = serde_json::from_value::<radicle::profile::Config>(raw.clone())
+                .map_err(|e| Error::InvalidConfig(e.to_string()))?;
+            let restart = NodeSetting::changed(&profile.config.node, &config.node);
+
+            // A rename within the same directory is atomic, so readers never
+            // see a partly written file.
+            let tmp = path.with_extension("json.tmp");
+            fs::write(&tmp, serde_json::to_string_pretty(&raw)? + "\n")?;
+            fs::rename(&tmp, &path)?;
+
+            // The alias is also what commits made through the storage are
+            // signed with.
+            if config.node.alias != profile.config.node.alias {
+                profile.storage = radicle::Storage::open(
+                    profile.home().storage(),
+                    radicle::git::UserInfo {
+                        alias: config.node.alias.clone(),
+                        key: profile.public_key,
+                    },
+                )?;
+            }
+            profile.config = config;
+
+            Ok(restart)
+        })?;
+
+        Ok(ConfigUpdate {
+            config: self.config(),
+            restart,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -40,15 +116,16 @@ mod test {
     use radicle::crypto::{Seed, Signer, SigningKey};
     use radicle::node::{Alias, config};
 
-    use crate::config::Config;
-    use crate::{AppState, Profile, test};
+    use crate::config::{Config, ConfigEdit, NodeSetting};
+    use crate::error::Error;
+    use crate::{AppState, Profile, ProfileMut, test};
 
     #[test]
     fn config() {
         let tmp = tempfile::tempdir().unwrap();
         let profile = test::profile(tmp.path(), [0xff; 32]);
         let signer = SigningKey::from_seed(Seed::new([0xff; 32]));
-        let state = AppState { profile };
+        let state = AppState::new(profile);
 
         assert_eq!(
             Profile::config(&state),
@@ -56,9 +133,60 @@ mod test {
                 public_key: *signer.public_key(),
                 alias: Alias::from_str("seed").unwrap(),
                 seeding_policy: config::DefaultSeedingPolicy::Block,
-                public_explorer: state.profile.config.public_explorer.clone(),
-                preferred_seeds: state.profile.config.preferred_seeds.clone(),
+                public_explorer: state.profile().config.public_explorer.clone(),
+                preferred_seeds: state.profile().config.preferred_seeds.clone(),
+                listen: vec![],
+                connect: vec![],
+                external_addresses: vec![],
             }
         )
     }
+
+    #[test]
+    fn update_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profile = test::profile(tmp.path(), [0xff; 32]);
+        let path = profile.home().config();
+        std::fs::write(
+            &path,
+            r#"{ "cli": { "hints": false }, "node": { "alias": "seed" } }"#,
+        )
+        .unwrap();
+        let state = AppState::new(profile);
+
+        let update = state
+            .update_config(ConfigEdit {
+                alias: Some("alice".to_string()),
+                preferred_seeds: Some(vec![]),
+                listen: Some(vec!["0.0.0.0:8776".to_string()]),
+                ..ConfigEdit::default()
+            })
+            .unwrap();
+        assert_eq!(update.config.alias, Alias::from_str("alice").unwrap());
+        assert!(update.config.preferred_seeds.is_empty());
+        assert_eq!(
+            update.restart,
+            vec![NodeSetting::Alias, NodeSetting::Listen]
+        );
+        assert_eq!(Profile::config(&state), update.config);
+        assert_eq!(
+            state.profile().storage.info().alias,
+            Alias::from_str("alice").unwrap()
+        );
+
+        let raw: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(raw["node"]["alias"], "alice");
+        assert_eq!(raw["cli"]["hints"], false);
+
+        let invalid = state.update_config(ConfigEdit {
+            listen: Some(vec!["nowhere".to_string()]),
+            ..ConfigEdit::default()
+        });
+        assert!(matches!(invalid, Err(Error::InvalidConfig(_))));
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            serde_json::to_string_pretty(&raw).unwrap() + "\n"
+        );
+    }
 }
@@ -0,0 +1,347 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::ControlFlow;
+use std::str::FromStr as _;
+
+use radicle::cob::cache::COBS_DB_FILE;
+use radicle::cob::{ObjectId, migrate};
+use radicle::identity::RepoId;
+use radicle::node::device::BoxedDevice;
+use radicle::storage::{ReadRepository, ReadStorage, RepositoryInfo, SignedRefsInfo};
+use radicle::{issue, patch, storage};
+
+use crate::diagnostics::{CacheHealth, CobKind, Problem, Repair, RepoHealth, Report};
+use crate::error::Error;
+use crate::outbound::sqlite::Sqlite;
+use crate::repo;
+use crate::traits::repo::Repo;
+
+/// The ids of the cached COBs, by repository and COB type name.
+type CachedIds = BTreeMap<(RepoId, String), BTreeSet<ObjectId>>;
+
+pub trait Diagnostics: Repo {
+    /// Check the COB cache and every repository in storage.
+    fn check_storage(&self) -> Result<Report, Error> {
+        let profile = self.profile();
+        let cache = match profile.home().cobs_db() {
+            Ok(cache) => match cache.check_version() {
+                Ok(()) => CacheHealth::Ok,
+                Err(e) => CacheHealth::Unusable {
+                    reason: e.to_string(),
+                },
+            },
+            Err(e) => CacheHealth::Unusable {
+                reason: e.to_string(),
+            },
+        };
+        // An unusable cache is reported once, for the whole cache.
+        let cached = matches!(cache, CacheHealth::Ok)
+            .then(|| cached_ids(&profile).ok())
+            .flatten();
+        let infos = profile.storage.repositories()?;
+        let checked = infos.len();
+        let repos = infos
+            .into_iter()
+            .map(|info| check_repo(&profile, info, cached.as_ref()))
+            .filter(|health| !health.problems.is_empty())
+            .collect();
+
+        Ok(Report::new(cache, checked, repos))
+    }
+
+    /// Migrate the COB cache and rebuild it for every repository, then check
+    /// storage again.
+    fn repair_cache(&self) -> Result<Report, Error> {
+        let profile = self.profile();
+        let signer = self.signer()?;
+        profile.home().cobs_db_mut()?.migrate(migrate::ignore)?;
+        for RepositoryInfo { rid, .. } in profile.storage.repositories()? {
+            rebuild_caches(&profile, &signer, rid)?;
+        }
+
+        self.check_storage()
+    }
+
+    /// Apply `repair` to `rid`, then check it again.
+    fn repair_repo(
+        &self,
+        rid: RepoId,
+        repair: Repair,
+        fetches: &repo::FetchLog,
+    ) -> Result<RepoHealth, Error> {
+        let profile = self.profile();
+
+        match repair {
+            Repair::RebuildCaches => {
+                rebuild_caches(&profile, &self.signer()?, rid)?;
+            }
+            Repair::Refetch => {
+                self.fetch_repo(rid, fetches, |_| {})?;
+            }
+        }
+
+        let health = match profile
+            .storage
+            .repositories()?
+            .into_iter()
+            .find(|info| info.rid == rid)
+        {
+            Some(info) => check_repo(&profile, info, cached_ids(&profile).ok().as_ref()),
+            None => RepoHealth::new(
+                rid,
+                None,
+                vec![Problem::Unreadable {
+                    reason: "the repository is not in storage".to_string(),
+                }],
+            ),
+        };
+
+        Ok(health)
+    }
+}
+
+/// Rewrite the issue and patch caches of `rid` from storage.
+fn rebuild_caches(
+    profile: &radicle::Profile,
+    signer: &BoxedDevice,
+    rid: RepoId,
+) -> Result<(), Error> {
+    let repo = profile.storage.repository(rid)?;
+    profile
+        .issues_mut(&repo, signer)?
+        .write_all(|_, _| ControlFlow::Continue(()))?;
+    profile
+        .patches_mut(&repo, signer)?
+        .write_all(|_, _| ControlFlow::Continue(()))?;
+
+    Ok(())
+}
+
+/// Read the ids of every COB in the cache.
+fn cached_ids(profile: &radicle::Profile) -> Result<CachedIds, Error> {
+    let mut ids = CachedIds::new();
+    Sqlite::reader(profile.cobs().join(COBS_DB_FILE))?.for_each_cob(|typename, rid, id, _| {
+        ids.entry((rid, typename.to_owned()))
+            .or_default()
+            .insert(id);
+        Ok(())
+    })?;
+
+    Ok(ids)
+}
+
+fn check_repo(
+    profile: &radicle::Profile,
+    info: RepositoryInfo,
+    cached: Option<&CachedIds>,
+) -> RepoHealth {
+    let RepositoryInfo { rid, doc, refs, .. } = info;
+    let name = doc.project().ok().map(|project| project.name().to_owned());
+    let mut problems = Vec::new();
+
+    if matches!(refs, SignedRefsInfo::NeedsMigration) {
+        problems.push(Problem::NeedsMigration {
+            remote: profile.public_key,
+        });
+    }
+    match profile.storage.repository(rid) {
+        Ok(repo) => {
+            problems.extend(signed_refs_problems(&repo));
+            problems.extend(cob_problems(&repo, cached));
+        }
+        Err(e) => problems.push(Problem::Unreadable {
+            reason: e.to_string(),
+        }),
+    }
+
+    RepoHealth::new(rid, name, problems)
+}
+
+/// Remotes whose signed refs can't be loaded and verified.
+fn signed_refs_problems(repo: &storage::git::Repository) -> Vec<Problem> {
+    let remotes = match repo.remote_ids() {
+        Ok(remotes) => remotes,
+        Err(e) => {
+            return vec![Problem::Unreadable {
+                reason: e.to_string(),
+            }];
+        }
+    };
+
+    remotes
+        .filter_map(|remote| match remote {
+            Ok(remote) => repo
+                .remote(&remote)
+                .err()
+                .map(|e| Problem::InvalidSignedRefs {
+                    remote,
+                    reason: e.to_string(),
+                }),
+            Err(e) => Some(Problem::Unreadable {
+                reason: e.to_string(),
+            }),
+        })
+        .collect()
+}
+
+/// COB refs pointing nowhere, and COB types whose cache entries aren't the
+/// objects in storage. The cache is only compared if it could be read.
+fn cob_problems(repo: &storage::git::Repository, cached: Option<&CachedIds>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut stored = BTreeMap::<String, BTreeSet<ObjectId>>::new();
+
+    let references = match repo
+        .backend
+        .references_glob("refs/namespaces/*/refs/cobs/*")
+    {
+        Ok(references) => references,
+        Err(e) => {
+            return vec![Problem::Unreadable {
+                reason: e.to_string(),
+            }];
+        }
+    };
+    for reference in references.flatten() {
+        let (Some(name), Some(oid)) = (reference.name(), reference.target()) else {
+            continue;
+        };
+        if repo.backend.find_object(oid, None).is_err() {
+            problems.push(Problem::DanglingCobRef {
+                reference: name.to_owned(),
+            });
+            continue;
+        }
+        // `refs/namespaces/<remote>/refs/cobs/<type>/<id>`
+        let mut parts = name.split('/').skip(5);
+        if let (Some(typename), Some(Ok(id))) = (parts.next(), parts.next().map(ObjectId::from_str))
+        {
+            stored.entry(typename.to_owned()).or_default().insert(id);
+        }
+    }
+    let Some(cached) = cached else {
+        return problems;
+    };
+
+    let none = BTreeSet::new();
+    for (cob, typename) in [
+        (CobKind::Issues, issue::TYPENAME.to_string()),
+        (CobKind::Patches, patch::TYPENAME.to_string()),
+    ] {
+        let cached = cached.get(&(repo.id, typename.clone())).unwrap_or(&none);
+        let stored = stored.get(&typename).unwrap_or(&none);
+        let missing = stored.difference(cached).count();
+        let stale = cached.difference(stored).count();
+
+        if missing > 0 || stale > 0 {
+            problems.push(Problem::CacheMismatch {
+                cob,
+                missing,
+                stale,
+            });
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use radicle::cob::cache::COBS_DB_FILE;
+    use radicle::storage::ReadStorage as _;
+
+    use crate::cobs::CobOptions;
+    use crate::cobs::issue::NewIssue;
+    use crate::diagnostics::{CacheHealth, CobKind, Problem, Repair, Report};
+    use crate::repo::FetchLog;
+    use crate::test;
+    use crate::traits::Profile as _;
+    use crate::traits::diagnostics::Diagnostics;
+    use crate::traits::issue::IssuesMut as _;
+
+    #[test]
+    fn dangling_cob_ref() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, _) = test::repo(tmp.path());
+        let profile = state.profile();
+
+        let report = state.check_storage().unwrap();
+        assert!(matches!(report.cache, CacheHealth::Ok));
+        assert_eq!(report.checked, 1);
+        assert!(report.repos.is_empty());
+
+        // Git refuses to create a ref to a missing object, so it's written
+        // by hand.
+        let repo = profile.storage.repository(rid).unwrap();
+        let missing = "8b2d1f4e0c7a4b1e9d6f3a2c5e8b7d0f1a3c6e9b";
+        let reference = repo.backend.path().join(format!(
+            "refs/namespaces/{}/refs/cobs/xyz.radicle.issue/{missing}",
+            profile.public_key
+        ));
+        std::fs::create_dir_all(reference.parent().unwrap()).unwrap();
+        std::fs::write(reference, format!("{missing}\n")).unwrap();
+
+        let report = state.check_storage().unwrap();
+        assert_eq!(report.repos.len(), 1);
+        assert!(matches!(
+            report.repos[0].problems[..],
+            [Problem::DanglingCobRef { .. }]
+        ));
+        assert_eq!(report.repos[0].repairs, vec![Repair::Refetch]);
+    }
+
+    #[test]
+    fn cache_mismatch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, _) = test::repo(tmp.path());
+        let profile = state.profile();
+        state
+            .create_issue(
+                rid,
+                NewIssue {
+                    title: "Crash on startup".to_owned(),
+                    description: String::new(),
+                    labels: vec![],
+                    assignees: vec![],
+                    embeds: vec![],
+                },
+                serde_json::from_str::<CobOptions>(r#"{ "announce": false }"#).unwrap(),
+            )
+            .unwrap();
+        sqlite::Connection::open(profile.cobs().join(COBS_DB_FILE))
+            .unwrap()
+            .execute("DELETE FROM issues")
+            .unwrap();
+
+        let report = state.check_storage().unwrap();
+        assert!(report.repairs.is_empty());
+        assert_eq!(report.repos.len(), 1);
+        assert!(matches!(
+            report.repos[0].problems[..],
+            [Problem::CacheMismatch {
+                cob: CobKind::Issues,
+                missing: 1,
+                stale: 0,
+            }]
+        ));
+        assert_eq!(report.repos[0].repairs, vec![Repair::RebuildCaches]);
+
+        let health = state
+            .repair_repo(rid, Repair::RebuildCaches, &FetchLog::default())
+            .unwrap();
+        assert!(health.problems.is_empty());
+        assert!(state.check_storage().unwrap().repos.is_empty());
+    }
+
+    #[test]
+    fn unusable_cache() {
+        let report = Report::new(
+            CacheHealth::Unusable {
+                reason: "out of date".to_owned(),
+            },
+            0,
+            vec![],
+        );
+        assert_eq!(report.repairs, vec![Repair::RebuildCaches]);
+        assert!(Report::new(CacheHealth::Ok, 0, vec![]).repairs.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use radicle::cob::Title;
+use radicle::cob::identity::{Identity, RevisionId};
+use radicle::identity::{Visibility, doc, project};
+use radicle::node::Handle;
+use radicle::storage::ReadStorage;
+use radicle::{Node, git, identity};
+
+use crate::cobs;
+use crate::error::Error;
+use crate::traits::Profile;
+
+pub trait Identities: Profile {
+    /// Every revision of the repository's identity, oldest first.
+    fn list_identity_revisions(
+        &self,
+        rid: identity::RepoId,
+    ) -> Result<Vec<cobs::identity::Revision>, Error> {
+        let profile = self.profile();
+        let repo = profile.storage.repository(rid)?;
+        let identity = Identity::load(&repo)?;
+        let current = identity.current().id;
+        let aliases = profile.aliases();
+
+        Ok(identity
+            .revisions()
+            .map(|revision| cobs::identity::Revision::new(revision, &current, &aliases))
+            .collect())
+    }
+
+    /// What `head` changes in the identity document compared to `base`, or to
+    /// the revision it was proposed on top of.
+    fn identity_revision_diff(
+        &self,
+        rid: identity::RepoId,
+        base: Option<RevisionId>,
+        head: RevisionId,
+    ) -> Result<Option<cobs::identity::DocumentDiff>, Error> {
+        let profile = self.profile();
+        let repo = profile.storage.repository(rid)?;
+        let identity = Identity::load(&repo)?;
+        let aliases = profile.aliases();
+
+        let Some(head) = identity.revision(&head) else {
+            return Ok(None);
+        };
+        let Some(base) = base
+            .or(head.parent)
+            .and_then(|base| identity.revision(&base))
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(cobs::identity::DocumentDiff::new(
+            base.doc(),
+            head.doc(),
+            &aliases,
+        )))
+    }
+}
+
+pub trait IdentitiesMut: Profile {
+    /// Propose a new revision of the identity document, based on the current
+    /// one. It takes effect once enough delegates accept it.
+    fn propose_identity_revision(
+        &self,
+        rid: identity::RepoId,
+        proposal: cobs::identity::Proposal,
+        opts: cobs::CobOptions,
+    ) -> Result<cobs::identity::Revision, Error> {
+        let profile = self.profile();
+        let mut node = Node::new(profile.home().socket_from_env());
+        let repo = profile.storage.repository(rid)?;
+        let signer = self.signer()?;
+        let aliases = profile.aliases();
+        let mut identity = Identity::load_mut(&repo)?;
+        let current = identity.current().doc().clone();
+
+        let mut raw = current.clone().edit();
+        for did in proposal.add_delegates {
+            if !raw.delegates.contains(&did) {
+                raw.delegates.push(did);
+            }
+        }
+        raw.delegates
+            .retain(|did| !proposal.remove_delegates.contains(did));
+        if let Some(threshold) = proposal.threshold {
+            raw.threshold = threshold;
+        }
+        if let Some(visibility) = proposal.visibility {
+            raw.visibility = Visibility::from(visibility);
+        }
+        if proposal.project_description.is_some() || proposal.default_branch.is_some() {
+            let project = current.project()?;
+            let project = project::Project::new(
+                project.name().try_into()?,
+                proposal
+                    .project_description
+                    .unwrap_or_else(|| project.description().to_owned()),
+                match proposal.default_branch {
+                    Some(branch) => git::fmt::RefString::try_from(branch)?,
+                    None => project.default_branch().clone(),
+                },
+            )?;
+            raw.payload.insert(
+                doc::PayloadId::project(),
+                doc::Payload::from(serde_json::to_value(project)?),
+            );
+        }
+        let doc = raw.verified()?;
+
+        let id = identity.update(
+            Title::try_from(proposal.title)?,
+            proposal.description,
+            &doc,
+            &signer,
+        )?;
+
+        if opts.announce()
+            && let Err(e) = node.announce_refs_for(rid, [profile.public_key])
+        {
+            log::error!("Not able to announce changes: {}", e)
+        }
+
+        let current = identity.current().id;
+        let revision = identity
+            .revision(&id)
+            .ok_or_else(|| Error::RevisionNotFound(id.to_string()))?;
+
+        Ok::<_, Error>(cobs::identity::Revision::new(revision, &current, &aliases))
+    }
+
+    /// Accept or reject a pending revision as a delegate.
+    fn edit_identity(
+        &self,
+        rid: identity::RepoId,
+        action: cobs::identity::Action,
+        opts: cobs::CobOptions,
+    ) -> Result<cobs::identity::Revision, Error> {
+        let profile = self.profile();
+        let mut node = Node::new(profile.home().socket_from_env());
+        let repo = profile.storage.repository(rid)?;
+        let signer = self.signer()?;
+        let aliases = profile.aliases();
+        let mut identity = Identity::load_mut(&repo)?;
+
+        let id = match action {
+            cobs::identity::Action::Accept { revision } => {
+                identity.accept(&revision, &signer)?;
+                revision
+            }
+            cobs::identity::Action::Reject { revision } => {
+                identity.reject(revision, &signer)?;
+                revision
+            }
+        };
+
+        if opts.announce()
+            && let Err(e) = node.announce_refs_for(rid, [profile.public_key])
+        {
+            log::error!("Not able to announce changes: {}", e)
+        }
+
+        let current = identity.current().id;
+        let revision = identity
+            .revision(&id)
+            .ok_or_else(|| Error::RevisionNotFound(id.to_string()))?;
+
+        Ok::<_, Error>(cobs::identity::Revision::new(revision, &current, &aliases))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use crate::cobs::CobOptions;
+    use crate::cobs::identity::{Proposal, State};
+    use crate::test;
+    use crate::traits::identity::{Identities, IdentitiesMut};
+
+    #[test]
+    fn propose_identity_revision() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, _) = test::repo(tmp.path());
+
+        let revision = state
+            .propose_identity_revision(
+                rid,
+                Proposal {
+                    title: "Describe the project".to_string(),
+                    description: String::new(),
+                    add_delegates: vec![],
+                    remove_delegates: vec![],
+                    threshold: None,
+                    visibility: None,
+                    project_description: Some("Anvils and more".to_string()),
+                    default_branch: None,
+                },
+                serde_json::from_str::<CobOptions>(r#"{ "announce": false }"#).unwrap(),
+            )
+            .unwrap();
+
+        // The only delegate proposed it, so it's accepted right away.
+        assert_eq!(revision.state, State::Accepted);
+        assert!(revision.current);
+        assert_eq!(
+            revision.document.project.unwrap().description,
+            "Anvils and more"
+        );
+
+        let diff = state
+            .identity_revision_diff(rid, None, revision.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(diff.description.unwrap().new, "Anvils and more");
+        assert!(diff.name.is_none());
+        assert_eq!(state.list_identity_revisions(rid).unwrap().len(), 2);
+    }
+}
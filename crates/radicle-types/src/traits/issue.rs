@@ -77,7 +77,7 @@ pub trait IssuesMut: Profile {
         let profile = self.profile();
         let mut node = Node::new(profile.home().socket_from_env());
         let repo = profile.storage.repository(rid)?;
-        let signer = profile.signer()?;
+        let signer = self.signer()?;
         let aliases = profile.aliases();
         let mut issues = profile.issues_mut(&repo, &signer)?;
         let title = Title::try_from(new.title)?;
@@ -108,7 +108,7 @@ pub trait IssuesMut: Profile {
         let profile = self.profile();
         let mut node = Node::new(profile.home().socket_from_env());
         let repo = profile.storage.repository(rid)?;
-        let signer = profile.signer()?;
+        let signer = self.signer()?;
         let aliases = profile.aliases();
         let mut issues = profile.issues_mut(&repo, &signer)?;
         let mut issue = issues.get_mut(&cob_id.into())?;
@@ -0,0 +1,153 @@
+use std::fs;
+
+use radicle::crypto::ssh::{self, Keystore, Passphrase};
+
+use crate::error::Error;
+use crate::keys::KeyInfo;
+use crate::traits::Profile;
+
+pub trait Keys: Profile {
+    fn key_info(&self) -> Result<KeyInfo, Error> {
+        let profile = self.profile();
+
+        Ok(KeyInfo {
+            did: profile.did(),
+            nid: profile.public_key,
+            fingerprint: ssh::fmt::fingerprint(&profile.public_key),
+            encrypted: profile.keystore.is_encrypted()?,
+        })
+    }
+
+    /// The public key in OpenSSH format, e.g. for an `authorized_keys` file.
+    fn export_public_key(&self) -> String {
+        ssh::fmt::key(&self.profile().public_key)
+    }
+
+    /// Encrypt the secret key with `new` instead of `current`. A key that's
+    /// not encrypted yet has no `current` passphrase.
+    fn change_passphrase(&self, current: Option<Passphrase>, new: Passphrase) -> Result<(), Error> {
+        if new.is_empty() {
+            return Err(Error::Crypto(ssh::keystore::Error::PassphraseMissing));
+        }
+        let profile = self.profile();
+        let keystore = &profile.keystore;
+        let secret = keystore
+            .secret_key(current)
+            .map_err(|e| {
+                if e.is_crypto_err() {
+                    Error::Crypto(ssh::keystore::Error::Ssh(ssh_key::Error::Crypto))
+                } else {
+                    e.into()
+                }
+            })?
+            .ok_or_else(|| radicle::profile::Error::NotFound(profile.home().keys()))?;
+
+        // The key pair is written to a keystore of its own first, and only
+        // moved over the current one once that succeeded.
+        let staging = profile.home().keys().with_extension("new");
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        let staged = Keystore::new(&staging);
+        staged.store(&secret, "radicle", Some(new))?;
+        fs::rename(staged.public_key_path(), keystore.public_key_path())?;
+        fs::rename(staged.secret_key_path(), keystore.secret_key_path())?;
+        fs::remove_dir_all(&staging)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use radicle::crypto::Signer as _;
+    use radicle::crypto::ssh::Passphrase;
+
+    use crate::traits::Profile as _;
+    use crate::traits::keys::Keys;
+    use crate::{AppState, test};
+
+    #[test]
+    fn change_passphrase() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profile = test::profile(tmp.path(), [0xff; 32]);
+        let state = AppState::new(profile);
+
+        let info = state.key_info().unwrap();
+        assert!(!info.encrypted);
+        assert!(info.fingerprint.starts_with("SHA256:"));
+        assert!(state.export_public_key().starts_with("ssh-ed25519 "));
+
+        state
+            .change_passphrase(None, Passphrase::from("secret".to_string()))
+            .unwrap();
+        assert!(state.key_info().unwrap().encrypted);
+        let profile = state.profile();
+        assert!(
+            profile
+                .keystore
+                .secret_key(Some(Passphrase::from("secret".to_string())))
+                .unwrap()
+                .is_some()
+        );
+
+        let wrong = state.change_passphrase(
+            Some(Passphrase::from("wrong".to_string())),
+            Passphrase::from("other".to_string()),
+        );
+        assert_eq!(
+            wrong.unwrap_err().code(),
+            "PassphraseError.InvalidPassphrase"
+        );
+        // The key still opens with the passphrase it had.
+        assert!(
+            profile
+                .keystore
+                .secret_key(Some(Passphrase::from("secret".to_string())))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn unlocked_key() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profile = test::profile(tmp.path(), [0xff; 32]);
+        let nid = profile.public_key;
+        let state = AppState::new(profile);
+        state
+            .change_passphrase(None, Passphrase::from("secret".to_string()))
+            .unwrap();
+        let secret = state
+            .profile()
+            .keystore
+            .secret_key(Some(Passphrase::from("secret".to_string())))
+            .unwrap()
+            .unwrap();
+
+        state.keys().unlock(nid, secret.clone());
+        state.keys().unlock(nid, secret.clone());
+        assert!(state.keys().is_unlocked(&nid));
+        assert!(state.keys().is_expiring());
+        assert_eq!(*state.signer().unwrap().public_key(), nid);
+
+        state.keys().set_timeout(Duration::ZERO);
+        assert!(!state.keys().is_unlocked(&nid));
+        assert!(state.keys().get(&nid).is_none());
+
+        // Shortening the timeout wakes the waiting thread, which drops the
+        // key and stops.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while state.keys().is_expiring() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!state.keys().is_expiring());
+
+        state.keys().set_timeout(Duration::from_secs(60));
+        state.keys().unlock(nid, secret);
+        assert!(state.keys().is_expiring());
+        state.keys().forget();
+    }
+}
@@ -0,0 +1,96 @@
+use radicle::node::routing::Store as _;
+use radicle::node::{Handle, Node};
+use radicle::storage::ReadStorage;
+
+use crate::error::Error;
+use crate::node;
+use crate::traits::Profile;
+
+pub trait NodeInfo: Profile {
+    /// Sessions, network settings, routing table size and inventory of the
+    /// node listening on the profile's control socket.
+    fn node_status(&self) -> Result<node::Status, Error> {
+        let profile = self.profile();
+        let handle = Node::new(profile.home().socket_from_env());
+        if !handle.is_running() {
+            return Ok(node::Status::Stopped);
+        }
+        let aliases = profile.aliases();
+
+        // The node may stop between the calls, which is no different from
+        // finding it stopped.
+        let (sessions, config) = match (handle.sessions(), handle.config()) {
+            (Ok(sessions), Ok(config)) => (sessions, config),
+            (Err(e), _) | (_, Err(e)) if e.is_connection_err() => {
+                return Ok(node::Status::Stopped);
+            }
+            (Err(e), _) | (_, Err(e)) => return Err(e.into()),
+        };
+
+        Ok(node::Status::Running {
+            sessions: sessions
+                .into_iter()
+                .map(|session| node::Session::new(session, &aliases))
+                .collect(),
+            config: config.into(),
+            routing_entries: profile.database()?.len()?,
+            inventory: profile.storage.inventory()?,
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    use crate::node::Status;
+    use crate::traits::node::NodeInfo;
+    use crate::{AppState, test};
+
+    /// Answer control socket commands the way a node without peers would.
+    fn fake_node(listener: UnixListener) {
+        let config = radicle::node::config::Config::new(radicle::node::Alias::new("seed"));
+
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+            let command: serde_json::Value = serde_json::from_str(&line).unwrap();
+
+            let response = match command["command"].as_str() {
+                Some("sessions") => serde_json::json!([]),
+                Some("config") => serde_json::to_value(&config).unwrap(),
+                _ => serde_json::json!({ "success": true }),
+            };
+            writeln!(stream, "{response}").unwrap();
+        }
+    }
+
+    #[test]
+    fn node_status() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profile = test::profile(tmp.path(), [0xff; 32]);
+        let socket = profile.home().socket();
+        let state = AppState::new(profile);
+
+        assert!(matches!(state.node_status().unwrap(), Status::Stopped));
+
+        let listener = UnixListener::bind(&socket).unwrap();
+        std::thread::spawn(move || fake_node(listener));
+
+        let Status::Running {
+            sessions,
+            config,
+            inventory,
+            ..
+        } = state.node_status().unwrap()
+        else {
+            panic!("the fake node answers on the control socket");
+        };
+        assert!(sessions.is_empty());
+        assert!(config.external_addresses.is_empty());
+        assert!(inventory.is_empty());
+    }
+}
@@ -1,15 +1,128 @@
 use std::collections::BTreeSet;
 
 use radicle::cob::Title;
-use radicle::node::Handle;
+use radicle::identity::DocAt;
+use radicle::node::{Handle, NodeId};
 use radicle::patch::cache::Patches as _;
-use radicle::storage::{ReadRepository as _, ReadStorage};
-use radicle::{Node, cob, git, identity};
+use radicle::storage::{
+    ReadRepository as _, ReadStorage, SignRepository as _, WriteRepository as _,
+};
+use radicle::{Node, cob, git, identity, storage};
 
 use crate::cobs;
 use crate::domain::patch::models;
 use crate::error::Error;
 use crate::traits::Profile;
+use crate::traits::repo::resolve_revision;
+
+/// Point our `refs/heads/patches/<id>` branch at a patch's head, so peers
+/// fetching our namespace get the commits the revision refers to. Our refs
+/// still need signing afterwards.
+fn publish_patch_head(
+    repo: &storage::git::Repository,
+    nid: &NodeId,
+    id: &cob::patch::PatchId,
+    head: git::Oid,
+) -> Result<(), Error> {
+    repo.backend.reference(
+        &format!("refs/namespaces/{nid}/refs/heads/patches/{id}"),
+        head.into(),
+        true,
+        &format!("Update patch {id}"),
+    )?;
+
+    Ok(())
+}
+
+/// Resolve a patch head given as a commit, or as a branch the way the source
+/// browser does, and pair it with its merge base against the canonical head of
+/// the default branch. Returns `(base, head)`.
+fn patch_base_and_head(
+    repo: &storage::git::Repository,
+    sha: Option<git::Oid>,
+    peer: Option<NodeId>,
+    branch: Option<String>,
+) -> Result<(git::Oid, git::Oid), Error> {
+    let head = match sha {
+        Some(sha) => sha,
+        None => resolve_revision(repo, peer, branch)?,
+    };
+    let (_, target) = repo.head()?;
+    let base = repo.backend.merge_base(target.into(), head.into())?;
+
+    Ok((base.into(), head))
+}
+
+/// Refuse a revision at the commit the patch is already at, since it would
+/// add a revision that changes nothing.
+fn ensure_new_head(patch: &cob::patch::Patch, head: git::Oid) -> Result<(), Error> {
+    if *patch.head() == head {
+        return Err(Error::PatchUpToDate);
+    }
+
+    Ok(())
+}
+
+/// Merge `revision` into our copy of the project's default branch, as a
+/// delegate's `git push rad` would, and return the branch's ref along with
+/// the commit it should end up at. A revision already reachable from the
+/// branch merges at the branch head, one that fast-forwards moves the branch
+/// to the revision head, and anything else gets a merge commit unless the two
+/// sides conflict. The branch itself is left alone, so the merge can be
+/// recorded on the patch before it moves.
+fn merge_revision(
+    repo: &storage::git::Repository,
+    nid: &NodeId,
+    user: &git::UserInfo,
+    revision: &cob::patch::Revision,
+) -> Result<(String, git::Oid), Error> {
+    let DocAt { doc, .. } = repo.identity_doc()?;
+    let branch = doc
+        .project()
+        .map_err(|e| Error::NotMergeable(e.to_string()))?
+        .default_branch()
+        .to_string();
+    let refname = format!("refs/namespaces/{nid}/refs/heads/{branch}");
+    // A delegate that never pushed the default branch merges on top of the
+    // canonical head instead.
+    let ours = match repo.backend.refname_to_id(&refname) {
+        Ok(oid) => oid,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {
+            let (_, head) = repo.head()?;
+            head.into()
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let theirs = revision.head().into();
+
+    if ours == theirs || repo.backend.graph_descendant_of(ours, theirs)? {
+        return Ok((refname, ours.into()));
+    }
+    if repo.backend.graph_descendant_of(theirs, ours)? {
+        return Ok((refname, theirs.into()));
+    }
+
+    let ours = repo.backend.find_commit(ours)?;
+    let theirs = repo.backend.find_commit(theirs)?;
+    let mut index = repo.backend.merge_commits(&ours, &theirs, None)?;
+    if index.has_conflicts() {
+        return Err(Error::NotMergeable(format!("conflicts with {branch}")));
+    }
+    let tree = repo
+        .backend
+        .find_tree(index.write_tree_to(&repo.backend)?)?;
+    let signature = git2::Signature::now(&user.name(), &user.email())?;
+    let merged = repo.backend.commit(
+        None,
+        &signature,
+        &signature,
+        &format!("Merge revision {}", revision.id()),
+        &tree,
+        &[&ours, &theirs],
+    )?;
+
+    Ok((refname, merged.into()))
+}
 
 pub trait Patches: Profile {
     fn get_patch(
@@ -51,6 +164,57 @@ pub trait Patches: Profile {
 }
 
 pub trait PatchesMut: Profile {
+    /// Open a patch proposing `new`'s head for merging into the target, with
+    /// its merge base against the canonical head as the base. Equivalent to
+    /// `git push rad HEAD:refs/patches`.
+    fn create_patch(
+        &self,
+        rid: identity::RepoId,
+        new: models::patch::NewPatch,
+        opts: cobs::CobOptions,
+    ) -> Result<models::patch::Patch, Error> {
+        let profile = self.profile();
+        let mut node = Node::new(profile.home().socket_from_env());
+        let repo = profile.storage.repository(rid)?;
+        let signer = self.signer()?;
+        let aliases = profile.aliases();
+        let (base, head) = patch_base_and_head(&repo, new.sha, new.peer, new.branch)?;
+        let title = Title::try_from(new.title)?;
+        let mut patches = profile.patches_mut(&repo, &signer)?;
+        let mut patch = if new.draft {
+            patches.draft(title, &new.description, new.target, base, head, &new.labels)?
+        } else {
+            patches.create(title, &new.description, new.target, base, head, &new.labels)?
+        };
+        // Opening a patch takes no embeds, so they are attached to the
+        // initial revision's description right after.
+        if !new.embeds.is_empty() {
+            let (revision, _) = patch.latest();
+            patch.edit_revision(
+                revision,
+                new.description,
+                new.embeds.into_iter().map(Into::into).collect::<Vec<_>>(),
+            )?;
+        }
+        publish_patch_head(&repo, &profile.public_key, patch.id(), head)?;
+        repo.sign_refs(&signer)?;
+
+        if opts.announce()
+            && let Err(e) = node.announce_refs_for(rid, [profile.public_key])
+        {
+            log::error!("Not able to announce changes: {}", e)
+        }
+
+        let delegates = Vec::from(repo.delegates()?);
+
+        Ok::<_, Error>(models::patch::Patch::new(
+            *patch.id(),
+            &patch,
+            &delegates,
+            &aliases,
+        ))
+    }
+
     /// Publish a review of a revision, together with its code comments.
     fn create_patch_review(
         &self,
@@ -58,7 +222,7 @@ pub trait PatchesMut: Profile {
     ) -> Result<cob::patch::ReviewId, Error> {
         let profile = self.profile();
         let repo = profile.storage.repository(args.rid)?;
-        let signer = profile.signer()?;
+        let signer = self.signer()?;
         let mut patches = profile.patches_mut(&repo, &signer)?;
         let patch_id = match patches.find_by_revision(&args.revision)? {
             Some(found) => found.id,
@@ -106,7 +270,7 @@ pub trait PatchesMut: Profile {
         let profile = self.profile();
         let mut node = Node::new(profile.home().socket_from_env());
         let repo = profile.storage.repository(rid)?;
-        let signer = profile.signer()?;
+        let signer = self.signer()?;
         let aliases = profile.aliases();
         let mut patches = profile.patches_mut(&repo, &signer)?;
         let mut patch = patches.get_mut(&cob_id.into())?;
@@ -267,11 +431,51 @@ pub trait PatchesMut: Profile {
             models::patch::Action::RevisionRedact { revision } => {
                 patch.redact(revision)?;
             }
-            models::patch::Action::Merge { .. } => {
-                unimplemented!("We don't support merging of patches through the desktop")
+            // The merge commit is computed here rather than taken from the
+            // caller, so what the patch records is always where our default
+            // branch points.
+            models::patch::Action::Merge { revision, .. } => {
+                if !repo.delegates()?.contains(&profile.public_key.into()) {
+                    return Err(Error::NotMergeable(
+                        "only delegates can merge patches".to_string(),
+                    ));
+                }
+                if !matches!(patch.state(), cob::patch::State::Open { .. }) {
+                    return Err(Error::NotMergeable("patch is not open".to_string()));
+                }
+                let Some(rev) = patch.revision(&revision).cloned() else {
+                    return Err(Error::NotMergeable(format!(
+                        "revision {revision} not found"
+                    )));
+                };
+                let (refname, commit) =
+                    merge_revision(&repo, &profile.public_key, profile.storage.info(), &rev)?;
+                // Recorded before the branch moves, so a failed merge never
+                // leaves the branch ahead of what the patch says.
+                patch.merge(revision, commit)?;
+                repo.backend.reference(
+                    &refname,
+                    commit.into(),
+                    true,
+                    &format!("Merge revision {revision}"),
+                )?;
+                repo.sign_refs(&signer)?;
+                // With a threshold above one the canonical head only moves once
+                // enough delegates have merged, which is not a failure.
+                if let Err(e) = repo.set_head() {
+                    log::warn!("Not able to update the canonical head: {e}");
+                }
             }
-            models::patch::Action::Revision { .. } => {
-                unimplemented!("We don't support creating new revisions through the desktop")
+            models::patch::Action::Revision {
+                description,
+                base,
+                oid,
+                ..
+            } => {
+                ensure_new_head(&patch, oid)?;
+                publish_patch_head(&repo, &profile.public_key, patch.id(), oid)?;
+                repo.sign_refs(&signer)?;
+                patch.update(description, base, oid)?;
             }
         }
 
@@ -291,6 +495,44 @@ pub trait PatchesMut: Profile {
         ))
     }
 
+    /// Append a revision to a patch, taking the merge base with the canonical
+    /// head of the default branch as its base. Equivalent to pushing a new
+    /// head to `patches/<id>` with `git push rad`.
+    fn create_patch_revision(
+        &self,
+        args: models::patch::CreateRevisionArgs,
+        opts: cobs::CobOptions,
+    ) -> Result<models::patch::Patch, Error> {
+        let profile = self.profile();
+        let mut node = Node::new(profile.home().socket_from_env());
+        let repo = profile.storage.repository(args.rid)?;
+        let signer = self.signer()?;
+        let aliases = profile.aliases();
+        let (base, head) = patch_base_and_head(&repo, args.sha, args.peer, args.branch)?;
+        let mut patches = profile.patches_mut(&repo, &signer)?;
+        let mut patch = patches.get_mut(&args.patch_id)?;
+
+        ensure_new_head(&patch, head)?;
+        publish_patch_head(&repo, &profile.public_key, &args.patch_id, head)?;
+        repo.sign_refs(&signer)?;
+        patch.update(args.description, base, head)?;
+
+        if opts.announce()
+            && let Err(e) = node.announce_refs_for(args.rid, [profile.public_key])
+        {
+            log::error!("Not able to announce changes: {}", e)
+        }
+
+        let delegates = Vec::from(repo.delegates()?);
+
+        Ok::<_, Error>(models::patch::Patch::new(
+            *patch.id(),
+            &patch,
+            &delegates,
+            &aliases,
+        ))
+    }
+
     /// Remove a patch COB. Equivalent to `rad patch delete`.
     ///
     /// Only the COB ref under our own namespace is dropped. On a patch we did
@@ -306,7 +548,7 @@ pub trait PatchesMut: Profile {
         let profile = self.profile();
         let mut node = Node::new(profile.home().socket_from_env());
         let repo = profile.storage.repository(rid)?;
-        let signer = profile.signer()?;
+        let signer = self.signer()?;
 
         // Remove via the cache-backed store so the patch is dropped from both
         // the git refs and the COB cache that listings read from; otherwise the
@@ -324,3 +566,331 @@ pub trait PatchesMut: Profile {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use radicle::cob::patch::{MergeTarget, PatchId};
+    use radicle::git;
+    use radicle::identity::RepoId;
+    use radicle::patch::cache::Patches as _;
+    use radicle::storage::{
+        ReadRepository as _, ReadStorage as _, SignRepository as _, WriteRepository as _,
+    };
+
+    use crate::cobs::CobOptions;
+    use crate::domain::patch::models;
+    use crate::error::Error;
+    use crate::traits::Profile as _;
+    use crate::traits::patch::PatchesMut;
+    use crate::{AppState, test};
+
+    /// Write a commit on top of `parent` straight into storage.
+    fn commit(
+        state: &AppState,
+        rid: RepoId,
+        parent: git::Oid,
+        path: &str,
+        content: &str,
+    ) -> git::Oid {
+        let repo = state.profile().storage.repository(rid).unwrap();
+        let parent = repo.backend.find_commit(parent.into()).unwrap();
+        let blob = repo.backend.blob(content.as_bytes()).unwrap();
+        let mut tree = repo
+            .backend
+            .treebuilder(Some(&parent.tree().unwrap()))
+            .unwrap();
+        tree.insert(path, blob, 0o100644).unwrap();
+        let tree = repo.backend.find_tree(tree.write().unwrap()).unwrap();
+        let signature = git2::Signature::now("anonymous", "anonymous@radicle.example").unwrap();
+
+        repo.backend
+            .commit(None, &signature, &signature, path, &tree, &[&parent])
+            .unwrap()
+            .into()
+    }
+
+    fn open_patch(state: &AppState, rid: RepoId, head: git::Oid) -> PatchId {
+        let new = models::patch::NewPatch {
+            title: "Add a file".to_string(),
+            description: String::new(),
+            sha: Some(head),
+            peer: None,
+            branch: None,
+            target: MergeTarget::Delegates,
+            labels: vec![],
+            draft: false,
+            embeds: vec![],
+        };
+        let patch =
+            serde_json::to_value(state.create_patch(rid, new, no_announce()).unwrap()).unwrap();
+
+        patch["id"].as_str().unwrap().parse().unwrap()
+    }
+
+    fn no_announce() -> CobOptions {
+        serde_json::from_str::<CobOptions>(r#"{ "announce": false }"#).unwrap()
+    }
+
+    fn merge(state: &AppState, rid: RepoId, id: PatchId) -> Result<models::patch::Patch, Error> {
+        let repo = state.profile().storage.repository(rid).unwrap();
+        let patch = state
+            .profile()
+            .patches(&repo)
+            .unwrap()
+            .get(&id)
+            .unwrap()
+            .unwrap();
+        let (revision, current) = patch.latest();
+
+        state.edit_patch(
+            rid,
+            **id,
+            models::patch::Action::Merge {
+                revision,
+                commit: current.head(),
+            },
+            no_announce(),
+        )
+    }
+
+    fn merged_at(state: &AppState, rid: RepoId, id: PatchId) -> Option<git::Oid> {
+        let repo = state.profile().storage.repository(rid).unwrap();
+        let patch = state
+            .profile()
+            .patches(&repo)
+            .unwrap()
+            .get(&id)
+            .unwrap()
+            .unwrap();
+
+        match patch.state() {
+            radicle::patch::State::Merged { commit, .. } => Some(*commit),
+            _ => None,
+        }
+    }
+
+    fn canonical_head(state: &AppState, rid: RepoId) -> git::Oid {
+        let (_, head) = state
+            .profile()
+            .storage
+            .repository(rid)
+            .unwrap()
+            .head()
+            .unwrap();
+
+        head
+    }
+
+    #[test]
+    fn create_patch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, base) = test::repo(tmp.path());
+        let head = commit(&state, rid, base, "README", "Hello, world!");
+        let id = open_patch(&state, rid, head);
+
+        let repo = state.profile().storage.repository(rid).unwrap();
+        let patch = state
+            .profile()
+            .patches(&repo)
+            .unwrap()
+            .get(&id)
+            .unwrap()
+            .unwrap();
+        let branch = repo
+            .backend
+            .refname_to_id(&format!(
+                "refs/namespaces/{}/refs/heads/patches/{id}",
+                state.profile().public_key
+            ))
+            .unwrap();
+
+        assert_eq!(*patch.base(), base);
+        assert_eq!(*patch.head(), head);
+        assert_eq!(git::Oid::from(branch), head);
+    }
+
+    fn new_revision(
+        state: &AppState,
+        rid: RepoId,
+        id: PatchId,
+        head: git::Oid,
+    ) -> Result<models::patch::Patch, Error> {
+        state.create_patch_revision(
+            models::patch::CreateRevisionArgs {
+                rid,
+                patch_id: id,
+                sha: Some(head),
+                peer: None,
+                branch: None,
+                description: "Update".to_string(),
+            },
+            no_announce(),
+        )
+    }
+
+    #[test]
+    fn revision_new_head() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, base) = test::repo(tmp.path());
+        let head = commit(&state, rid, base, "README", "Hello, world!");
+        let id = open_patch(&state, rid, head);
+        let update = commit(&state, rid, head, "README", "Hello, again!");
+
+        new_revision(&state, rid, id, update).unwrap();
+
+        let repo = state.profile().storage.repository(rid).unwrap();
+        let patch = state
+            .profile()
+            .patches(&repo)
+            .unwrap()
+            .get(&id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(patch.revisions().count(), 2);
+        assert_eq!(*patch.head(), update);
+        assert_eq!(*patch.base(), base);
+    }
+
+    #[test]
+    fn revision_unchanged_head() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, base) = test::repo(tmp.path());
+        let head = commit(&state, rid, base, "README", "Hello, world!");
+        let id = open_patch(&state, rid, head);
+
+        assert!(matches!(
+            new_revision(&state, rid, id, head),
+            Err(Error::PatchUpToDate)
+        ));
+        assert!(matches!(
+            state.edit_patch(
+                rid,
+                **id,
+                models::patch::Action::Revision {
+                    description: "Update".to_string(),
+                    base,
+                    oid: head,
+                    resolves: Default::default(),
+                },
+                no_announce(),
+            ),
+            Err(Error::PatchUpToDate)
+        ));
+    }
+
+    #[test]
+    fn revision_merge_base() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, base) = test::repo(tmp.path());
+        let head = commit(&state, rid, base, "README", "Hello, world!");
+        let id = open_patch(&state, rid, head);
+
+        // Move the canonical head on and rebase the patch onto it.
+        let upstream = commit(&state, rid, base, "LICENSE", "MIT");
+        let repo = state.profile().storage.repository(rid).unwrap();
+        repo.backend
+            .reference(
+                &format!(
+                    "refs/namespaces/{}/refs/heads/master",
+                    state.profile().public_key
+                ),
+                upstream.into(),
+                true,
+                "",
+            )
+            .unwrap();
+        repo.sign_refs(&state.signer().unwrap()).unwrap();
+        repo.set_head().unwrap();
+        let rebased = commit(&state, rid, upstream, "README", "Hello, world!");
+
+        new_revision(&state, rid, id, rebased).unwrap();
+
+        let patch = state
+            .profile()
+            .patches(&repo)
+            .unwrap()
+            .get(&id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(*patch.base(), upstream);
+        assert_eq!(*patch.head(), rebased);
+    }
+
+    #[test]
+    fn merge_fast_forward() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, base) = test::repo(tmp.path());
+        let head = commit(&state, rid, base, "README", "Hello, world!");
+        let id = open_patch(&state, rid, head);
+
+        merge(&state, rid, id).unwrap();
+
+        assert_eq!(merged_at(&state, rid, id), Some(head));
+        assert_eq!(canonical_head(&state, rid), head);
+    }
+
+    #[test]
+    fn merge_diverged_creates_merge_commit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, base) = test::repo(tmp.path());
+        let head = commit(&state, rid, base, "README", "Hello, world!");
+        let id = open_patch(&state, rid, head);
+
+        // Move the default branch on, so the patch no longer fast-forwards.
+        let upstream = commit(&state, rid, base, "LICENSE", "MIT");
+        let repo = state.profile().storage.repository(rid).unwrap();
+        repo.backend
+            .reference(
+                &format!(
+                    "refs/namespaces/{}/refs/heads/master",
+                    state.profile().public_key
+                ),
+                upstream.into(),
+                true,
+                "",
+            )
+            .unwrap();
+
+        merge(&state, rid, id).unwrap();
+
+        let merged = merged_at(&state, rid, id).unwrap();
+        let commit = repo.backend.find_commit(merged.into()).unwrap();
+        let parents = commit.parent_ids().map(git::Oid::from).collect::<Vec<_>>();
+
+        assert_eq!(parents, vec![upstream, head]);
+        assert_eq!(
+            commit.author().email(),
+            Some(state.profile().storage.info().email().as_str())
+        );
+        assert_eq!(canonical_head(&state, rid), merged);
+    }
+
+    #[test]
+    fn merge_conflict_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, base) = test::repo(tmp.path());
+        let head = commit(&state, rid, base, "README", "Hello, world!");
+        let id = open_patch(&state, rid, head);
+
+        let upstream = commit(&state, rid, base, "README", "Goodbye, world!");
+        let repo = state.profile().storage.repository(rid).unwrap();
+        repo.backend
+            .reference(
+                &format!(
+                    "refs/namespaces/{}/refs/heads/master",
+                    state.profile().public_key
+                ),
+                upstream.into(),
+                true,
+                "",
+            )
+            .unwrap();
+
+        assert!(matches!(
+            merge(&state, rid, id),
+            Err(Error::NotMergeable(_))
+        ));
+        assert_eq!(merged_at(&state, rid, id), None);
+    }
+}
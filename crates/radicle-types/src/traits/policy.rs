@@ -0,0 +1,135 @@
+use radicle::identity::RepoId;
+use radicle::node::{Alias, AliasStore, Handle, NodeId};
+
+use crate::error::Error;
+use crate::policy;
+use crate::traits::Profile;
+
+pub trait Policies: Profile {
+    /// Every peer we follow or block.
+    fn list_follow_policies(&self) -> Result<Vec<policy::FollowPolicy>, Error> {
+        let profile = self.profile();
+        let policies = profile.policies()?;
+        let aliases = profile.aliases();
+        let mut entries = Vec::new();
+
+        for entry in policies.follow_policies()? {
+            let Ok(entry) = entry else { continue };
+
+            entries.push(policy::FollowPolicy {
+                nid: entry.nid,
+                alias: entry.alias.or_else(|| aliases.alias(&entry.nid)),
+                policy: entry.policy.into(),
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Follow a peer, optionally giving it an alias. Goes through the node
+    /// when it's running, so it takes effect right away.
+    ///
+    /// Returns `false` if we already followed the peer with that alias.
+    fn follow(&self, nid: NodeId, alias: Option<Alias>) -> Result<bool, Error> {
+        let profile = self.profile();
+        let mut node = radicle::Node::new(profile.home().socket_from_env());
+
+        match node.follow(nid, alias.clone()) {
+            Ok(updated) => Ok(updated),
+            Err(e) if e.is_connection_err() => {
+                let mut policies = profile.policies_mut()?;
+
+                Ok(policies.follow(&nid, alias.as_ref())?)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Stop following a peer. Returns `false` if we weren't following it.
+    fn unfollow(&self, nid: NodeId) -> Result<bool, Error> {
+        let profile = self.profile();
+        let mut node = radicle::Node::new(profile.home().socket_from_env());
+
+        match node.unfollow(nid) {
+            Ok(updated) => Ok(updated),
+            Err(e) if e.is_connection_err() => {
+                let mut policies = profile.policies_mut()?;
+
+                Ok(policies.unfollow(&nid)?)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Never fetch from this peer, replacing any follow policy it had.
+    ///
+    /// The node has no command for blocking, so when it's running we
+    /// unfollow the peer through it first, for it to stop fetching from the
+    /// peer right away, and then record the block.
+    fn block_node(&self, nid: NodeId) -> Result<bool, Error> {
+        let profile = self.profile();
+        let mut node = radicle::Node::new(profile.home().socket_from_env());
+
+        match node.unfollow(nid) {
+            Ok(_) => {}
+            Err(e) if e.is_connection_err() => {}
+            Err(e) => return Err(e.into()),
+        }
+        let mut policies = profile.policies_mut()?;
+
+        Ok(policies.block_nid(&nid)?)
+    }
+
+    /// Never fetch this repository, replacing any seed policy it had. Like
+    /// [`Policies::block_node`], it's unseeded through the node first when
+    /// the node is running.
+    fn block_repo(&self, rid: RepoId) -> Result<bool, Error> {
+        let profile = self.profile();
+        let mut node = radicle::Node::new(profile.home().socket_from_env());
+
+        match node.unseed(rid) {
+            Ok(_) => {}
+            Err(e) if e.is_connection_err() => {}
+            Err(e) => return Err(e.into()),
+        }
+        let mut policies = profile.policies_mut()?;
+
+        Ok(policies.block_repo(&rid)?)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use radicle::node::Alias;
+
+    use crate::policy::Policy;
+    use crate::traits::policy::Policies;
+    use crate::{AppState, test};
+
+    #[test]
+    fn follow_unfollow_block() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profile = test::profile(&tmp.path().join("alice"), [0xff; 32]);
+        let bob = test::profile(&tmp.path().join("bob"), [0xaa; 32]).public_key;
+        let state = AppState::new(profile);
+
+        assert!(state.follow(bob, Some(Alias::new("bob"))).unwrap());
+        let bob_policy = |state: &AppState| {
+            state
+                .list_follow_policies()
+                .unwrap()
+                .into_iter()
+                .find(|p| p.nid == bob)
+        };
+        let followed = bob_policy(&state).unwrap();
+        assert_eq!(followed.alias, Some(Alias::new("bob")));
+        assert_eq!(followed.policy, Policy::Allow);
+
+        assert!(state.block_node(bob).unwrap());
+        assert_eq!(bob_policy(&state).unwrap().policy, Policy::Block);
+
+        assert!(state.unfollow(bob).unwrap());
+        assert!(bob_policy(&state).is_none());
+    }
+}
@@ -1,13 +1,16 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use base64::Engine;
 use radicle_surf as surf;
 use serde::{Deserialize, Serialize};
 
+use radicle::cob::cache::COBS_DB_FILE;
 use radicle::identity::{Doc, DocAt, doc};
 use radicle::issue::cache::Issues as _;
-use radicle::node::AliasStore;
+use radicle::node::config::DefaultSeedingPolicy;
 use radicle::node::routing::Store;
+use radicle::node::seed::Store as _;
+use radicle::node::{AliasStore, Handle};
 use radicle::patch::cache::Patches as _;
 use radicle::storage;
 use radicle::storage::{ReadRepository, ReadStorage, RepositoryInfo};
@@ -16,6 +19,8 @@ use radicle::{git, identity, node};
 use crate::cobs;
 use crate::diff;
 use crate::diff::Diff;
+use crate::domain::inbox::models::notification::{NotificationState, SetStatusNotifications};
+use crate::domain::inbox::traits::InboxService;
 use crate::error::Error;
 use crate::repo;
 use crate::source;
@@ -29,6 +34,12 @@ const CREATE_NO_WINDOW: u32 = 0x08000000;
 
 pub const MAX_BLOB_SIZE: usize = 10_485_760;
 
+/// How long to wait for a single seed to serve a fetch.
+const FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long to wait for seeds to sync our refs after announcing them.
+const SYNC_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 #[derive(Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum Show {
@@ -55,7 +66,7 @@ fn is_contributor(refs: &storage::SignedRefsInfo) -> bool {
 /// revision, a peer resolves to its head of the project's default branch, and
 /// no peer resolves to the canonical head. Raw commit OIDs are passed through
 /// the handlers' `sha`/`head` argument instead of this function.
-fn resolve_revision(
+pub(crate) fn resolve_revision(
     repo: &storage::git::Repository,
     peer: Option<node::NodeId>,
     revision: Option<String>,
@@ -274,6 +285,80 @@ fn canonical_refs(repo: &storage::git::Repository) -> Result<repo::Canonical, Er
     Ok(canonical)
 }
 
+/// The scope to seed with: `scope` if one is picked, otherwise the one of the
+/// default seeding `policy`.
+fn seeding_scope(scope: Option<repo::Scope>, policy: DefaultSeedingPolicy) -> node::policy::Scope {
+    match (scope, policy) {
+        (Some(scope), _) => scope.into(),
+        (None, DefaultSeedingPolicy::Allow { scope }) => scope,
+        // Seeding is asked for explicitly, so a blocking default doesn't say
+        // which peers to fetch from.
+        (None, DefaultSeedingPolicy::Block) => node::policy::Scope::All,
+    }
+}
+
+/// Where `name` points: the commit for branches, the object itself for tags.
+fn ref_oid(repo: &storage::git::Repository, name: &str, branch: bool) -> Option<git::Oid> {
+    let r = repo.backend.find_reference(name).ok()?;
+
+    if branch {
+        r.peel_to_commit().ok().map(|commit| commit.id().into())
+    } else {
+        r.target().map(Into::into)
+    }
+}
+
+/// Whether the commit `id` is `head` or one of its ancestors. An id that
+/// isn't a commit in the repository is in no history.
+fn is_ancestor(repo: &storage::git::Repository, head: git::Oid, id: &str) -> bool {
+    let Ok(id) = git::raw::Oid::from_str(id) else {
+        return false;
+    };
+
+    let head = git::raw::Oid::from(head);
+
+    id == head || repo.backend.graph_descendant_of(head, id).unwrap_or(false)
+}
+
+/// Which object, if any, the `votes` for a ref agree on. A vote for a branch
+/// also counts for every ancestor of the commit it points at, so the quorum is
+/// the newest commit at least `threshold` peers have. Tag votes only count
+/// when they match exactly.
+fn quorum(
+    repo: &storage::git::Repository,
+    votes: &[git::Oid],
+    threshold: usize,
+    branch: bool,
+) -> repo::Quorum {
+    let includes = |head: git::Oid, oid: git::Oid| {
+        head == oid
+            || (branch
+                && repo
+                    .backend
+                    .graph_descendant_of(head.into(), oid.into())
+                    .unwrap_or(false))
+    };
+    let supported = votes
+        .iter()
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|oid| votes.iter().filter(|v| includes(**v, *oid)).count() >= threshold)
+        .collect::<Vec<_>>();
+    // Commits included in another supported one are behind it.
+    let candidates = supported
+        .iter()
+        .copied()
+        .filter(|oid| !supported.iter().any(|o| o != oid && includes(*o, *oid)))
+        .collect::<Vec<_>>();
+
+    match candidates.as_slice() {
+        [] => repo::Quorum::NotReached,
+        [oid] => repo::Quorum::Reached { oid: *oid },
+        _ => repo::Quorum::Diverged { candidates },
+    }
+}
+
 /// Resolve a ref OID to a [`repo::Tag`]. For annotated tags uses tagger time;
 /// for lightweight tags uses the target commit's time. Returns `None` if the
 /// OID cannot be peeled to a commit.
@@ -351,6 +436,165 @@ fn partition_refs(
     (branches, tags)
 }
 
+/// The peers our node has an established session with.
+fn connected_peers(node: &radicle::Node) -> Result<BTreeSet<node::NodeId>, node::Error> {
+    Ok(node
+        .sessions()?
+        .into_iter()
+        .filter(|session| matches!(session.state, node::State::Connected { .. }))
+        .map(|session| session.nid)
+        .collect())
+}
+
+/// Progress event for fetching from `nid`.
+fn fetch_event(
+    nid: node::NodeId,
+    result: Result<node::FetchResult, node::Error>,
+) -> cobs::repo::SyncEvent {
+    match result {
+        Ok(node::FetchResult::Success { updated, .. }) => cobs::repo::SyncEvent::Fetched {
+            nid,
+            updated: updated.len(),
+        },
+        Ok(node::FetchResult::Failed { reason }) => {
+            cobs::repo::SyncEvent::FetchFailed { nid, reason }
+        }
+        Err(e) => cobs::repo::SyncEvent::FetchFailed {
+            nid,
+            reason: e.to_string(),
+        },
+    }
+}
+
+/// The size of everything under `path`, in bytes. Entries that can't be read
+/// are skipped.
+fn dir_size(path: &std::path::Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map_or(0, |metadata| metadata.len()),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// The disk space `rid` takes. With `sizes`, also what each remote's refs
+/// reach, which walks the history twice per remote.
+fn repo_usage(
+    profile: &radicle::Profile,
+    rid: identity::RepoId,
+    name: Option<String>,
+    sizes: bool,
+) -> Result<repo::RepoUsage, Error> {
+    let repo = profile.storage.repository(rid)?;
+    let aliases = profile.aliases();
+    let path = repo.backend.path();
+    let objects = path.join("objects");
+    let packs = dir_size(&objects.join("pack"));
+    let loose_objects = dir_size(&objects)
+        .saturating_sub(packs)
+        .saturating_sub(dir_size(&objects.join("info")));
+
+    let mut remotes = Vec::new();
+    for nid in repo.remote_ids()? {
+        let nid = nid?;
+        let namespace = format!("refs/namespaces/{nid}/*");
+        let refs = repo.backend.references_glob(&namespace)?.count();
+        let (size, unique) = if sizes {
+            let size = reachable_size(path, &[format!("--glob={namespace}")])?;
+            let unique = reachable_size(
+                path,
+                &[
+                    format!("--glob={namespace}"),
+                    "--not".to_owned(),
+                    format!("--exclude={namespace}"),
+                    "--glob=refs/namespaces/*".to_owned(),
+                ],
+            )?;
+            (Some(size), Some(unique))
+        } else {
+            (None, None)
+        };
+        remotes.push(repo::RemoteUsage {
+            alias: aliases.alias(&nid),
+            nid,
+            refs,
+            size,
+            unique,
+        });
+    }
+
+    Ok(repo::RepoUsage {
+        rid,
+        name,
+        total: dir_size(path),
+        loose_objects,
+        packs,
+        remotes,
+        error: None,
+    })
+}
+
+/// The project name of `repo`, if its identity document can be read.
+fn project_name(repo: &storage::git::Repository) -> Option<String> {
+    repo.identity_doc()
+        .ok()
+        .and_then(|DocAt { doc, .. }| doc.project().ok())
+        .map(|project| project.name().to_owned())
+}
+
+/// Like [`repo_usage`], but a repository that can't be read gets an entry
+/// with the error, so one broken repository doesn't hide what the others take.
+fn usage_or_error(
+    profile: &radicle::Profile,
+    rid: identity::RepoId,
+    name: Option<String>,
+) -> repo::RepoUsage {
+    repo_usage(profile, rid, name.clone(), false).unwrap_or_else(|e| repo::RepoUsage {
+        rid,
+        name,
+        total: dir_size(&profile.storage.path_of(&rid)),
+        loose_objects: 0,
+        packs: 0,
+        remotes: Vec::new(),
+        error: Some(e.to_string()),
+    })
+}
+
+/// The disk space taken by the objects reachable from `revs`, in bytes, as
+/// `git rev-list --disk-usage` counts it.
+fn reachable_size(path: &std::path::Path, revs: &[String]) -> Result<u64, Error> {
+    let mut command = std::process::Command::new("git");
+    command
+        .current_dir(path)
+        .arg("rev-list")
+        .arg("--objects")
+        .arg("--disk-usage")
+        .args(revs);
+    #[cfg(windows)]
+    command.creation_flags(CREATE_NO_WINDOW);
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        )
+        .into());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stdout.trim() {
+        "" => Ok(0),
+        size => size
+            .parse()
+            .map_err(|e| std::io::Error::other(format!("invalid disk usage `{size}`: {e}")).into()),
+    }
+}
+
 pub trait Repo: Profile {
     fn list_repos(&self, show: Show) -> Result<Vec<repo::RepoInfo>, Error> {
         let profile = self.profile();
@@ -593,6 +837,7 @@ pub trait Repo: Profile {
         let DocAt { doc, .. } = repo.identity_doc()?;
         let delegates = doc.delegates();
         let aliases = profile.aliases();
+        let policies = profile.policies()?;
 
         let mut remotes = Vec::new();
         for entry in repo.remotes()? {
@@ -602,6 +847,7 @@ pub trait Repo: Profile {
                 id,
                 alias: aliases.alias(&id),
                 delegate: delegates.contains(&id.into()),
+                followed: policies.is_following(&id)?,
                 branches,
                 tags,
             });
@@ -612,6 +858,87 @@ pub trait Repo: Profile {
         Ok(repo::RepoRefs { canonical, remotes })
     }
 
+    /// The canonical-refs rules of the repository, with how the allowed peers
+    /// vote on every branch and tag each rule covers. Refs are listed if they
+    /// exist in storage or under any of the allowed peers.
+    fn canonical_rules(&self, rid: identity::RepoId) -> Result<Vec<repo::CanonicalRule>, Error> {
+        let profile = self.profile();
+        let repo = profile.storage.repository(rid)?;
+        let aliases = profile.aliases();
+        let DocAt { doc, .. } = repo.identity_doc()?;
+        let crefs = doc
+            .canonical_refs()
+            .map_err(storage::RepositoryError::from)?;
+        let rules = git::canonical::rules::RawRules::from(crefs.rules().clone());
+
+        let mut result = Vec::new();
+        for (pattern, rule) in rules.iter() {
+            let allowed = match rule.allowed() {
+                git::canonical::rules::Allowed::Delegates => {
+                    doc.delegates().iter().copied().collect::<Vec<_>>()
+                }
+                git::canonical::rules::Allowed::Set(dids) => dids.iter().copied().collect(),
+            };
+            let threshold = *rule.threshold();
+
+            let mut names = BTreeSet::new();
+            for prefix in std::iter::once(String::new()).chain(
+                allowed
+                    .iter()
+                    .map(|did| format!("refs/namespaces/{}/", did.as_key())),
+            ) {
+                for r in repo
+                    .backend
+                    .references_glob(&format!("{prefix}{}", pattern.as_str()))?
+                {
+                    let r = r?;
+                    if let Some(name) = r.name().ok().and_then(|n| n.strip_prefix(&prefix)) {
+                        names.insert(name.to_owned());
+                    }
+                }
+            }
+
+            let refs = names
+                .into_iter()
+                .filter(|name| name.starts_with("refs/heads/") || name.starts_with("refs/tags/"))
+                .map(|name| {
+                    let branch = name.starts_with("refs/heads/");
+                    let votes = allowed
+                        .iter()
+                        .map(|did| repo::Vote {
+                            peer: cobs::Author::new(did, &aliases),
+                            oid: ref_oid(
+                                &repo,
+                                &format!("refs/namespaces/{}/{name}", did.as_key()),
+                                branch,
+                            ),
+                        })
+                        .collect::<Vec<_>>();
+                    let oids = votes.iter().filter_map(|v| v.oid).collect::<Vec<_>>();
+
+                    repo::RefQuorum {
+                        canonical: ref_oid(&repo, &name, branch),
+                        quorum: quorum(&repo, &oids, threshold, branch),
+                        name,
+                        votes,
+                    }
+                })
+                .collect();
+
+            result.push(repo::CanonicalRule {
+                pattern: pattern.as_str().to_owned(),
+                allowed: allowed
+                    .iter()
+                    .map(|did| cobs::Author::new(did, &aliases))
+                    .collect(),
+                threshold,
+                refs,
+            });
+        }
+
+        Ok(result)
+    }
+
     fn repo_by_id(&self, rid: identity::RepoId) -> Result<repo::RepoInfo, Error> {
         let profile = self.profile();
         let repo = profile.storage.repository(rid)?;
@@ -688,15 +1015,7 @@ pub trait Repo: Profile {
             payloads: repo::SupportedPayloads { project },
             delegates,
             threshold: doc.threshold(),
-            visibility: match doc.visibility().clone() {
-                identity::Visibility::Public => repo::Visibility::Public,
-                identity::Visibility::Private { allow } => repo::Visibility::Private {
-                    allow: allow
-                        .iter()
-                        .map(|did| cobs::Author::new(did, &aliases))
-                        .collect(),
-                },
-            },
+            visibility: repo::Visibility::new(doc.visibility(), &aliases),
             rid: repo.id,
             seeding,
             last_commit_timestamp: commit.time().seconds() * 1000,
@@ -798,6 +1117,11 @@ pub trait Repo: Profile {
         Ok(commits)
     }
 
+    /// One page of the history of `head`, or of the resolved revision. A
+    /// page starts after the commit `after` points at when set, else `skip`
+    /// commits in. If that commit is no longer in the history, e.g. after a
+    /// force-push, the page starts at the first commit older than it.
+    #[allow(clippy::too_many_arguments)]
     fn list_repo_commits(
         &self,
         rid: identity::RepoId,
@@ -805,6 +1129,7 @@ pub trait Repo: Profile {
         peer: Option<node::NodeId>,
         revision: Option<String>,
         skip: Option<usize>,
+        after: Option<crate::cobs::Cursor>,
         take: Option<usize>,
     ) -> Result<crate::cobs::PaginatedQuery<Vec<repo::Commit>>, Error> {
         let profile = self.profile();
@@ -816,37 +1141,55 @@ pub trait Repo: Profile {
         };
 
         let repo = surf::Repository::open(storage_repo.path())?;
-        let commits = repo.history(oid)?;
-        let cursor = skip.unwrap_or(0);
+        let commits = repo
+            .history(oid)?
+            .filter_map(|c| c.map(Into::<repo::Commit>::into).ok());
+        let cursor = if after.is_some() {
+            0
+        } else {
+            skip.unwrap_or(0)
+        };
+        // Commits only ever get added on top of the ones a previous page
+        // listed, so resuming after a commit still in the history picks up
+        // exactly where that page ended. A commit that was dropped leaves its
+        // commit time to resume from.
+        let commits: Box<dyn Iterator<Item = repo::Commit> + '_> = match after {
+            Some(after) if is_ancestor(&storage_repo, oid, &after.id) => Box::new(
+                commits
+                    .skip_while(move |c| c.id.to_string() != after.id)
+                    .skip(1),
+            ),
+            Some(after) => {
+                Box::new(commits.skip_while(move |c| c.committer.time.seconds() >= after.key))
+            }
+            None => Box::new(commits),
+        };
 
         match take {
-            None => {
-                let content: Vec<repo::Commit> =
-                    commits.filter_map(|c| c.map(Into::into).ok()).collect();
-
-                Ok(crate::cobs::PaginatedQuery {
-                    cursor: 0,
-                    more: false,
-                    content,
-                })
-            }
+            None => Ok(crate::cobs::PaginatedQuery {
+                cursor: 0,
+                more: false,
+                content: commits.collect(),
+                next: None,
+            }),
             Some(take) => {
-                let content: Vec<repo::Commit> = commits
-                    .filter_map(|c| c.map(Into::into).ok())
-                    .skip(cursor)
-                    .take(take + 1)
-                    .collect();
+                let mut content: Vec<repo::Commit> = commits.skip(cursor).take(take + 1).collect();
                 let more = content.len() > take;
-                let content = if more {
-                    content[..take].to_vec()
+                content.truncate(take);
+                let next = if more {
+                    content.last().map(|c| crate::cobs::Cursor {
+                        key: c.committer.time.seconds(),
+                        id: c.id.to_string(),
+                    })
                 } else {
-                    content
+                    None
                 };
 
                 Ok(crate::cobs::PaginatedQuery {
                     cursor,
                     more,
                     content,
+                    next,
                 })
             }
         }
@@ -917,6 +1260,43 @@ pub trait Repo: Profile {
         Ok(commit.into())
     }
 
+    /// Turn the git working copy at `args.path` into a Radicle repository,
+    /// like `rad init` does: this creates the identity document and the
+    /// repository in storage, adds the `rad` remote to the working copy and
+    /// pushes the default branch to it.
+    fn init_repo(&self, args: repo::InitRepo) -> Result<identity::RepoId, Error> {
+        let profile = self.profile();
+        let signer = self.signer()?;
+        let working = git::raw::Repository::open(&args.path)?;
+        let visibility = identity::Visibility::from(args.visibility);
+        let public = visibility.is_public();
+
+        let (rid, _, _) = radicle::rad::init(
+            &working,
+            args.name.try_into()?,
+            &args.description,
+            git::fmt::RefString::try_from(args.default_branch)?,
+            visibility,
+            &signer,
+            &profile.storage,
+        )?;
+
+        if args.seed {
+            let scope = seeding_scope(args.scope, profile.config.node.seeding_policy);
+            let mut node = radicle::Node::new(profile.home().socket_from_env());
+            profile.seed(rid, scope, &mut node)?;
+
+            if public
+                && node.is_running()
+                && let Err(e) = node.announce_refs_for(rid, [profile.public_key])
+            {
+                log::error!("Not able to announce the new repository: {}", e)
+            }
+        }
+
+        Ok(rid)
+    }
+
     fn unseed(&self, rid: identity::RepoId) -> Result<(), Error> {
         let profile = self.profile();
         let mut node = radicle::Node::new(profile.home().socket_from_env());
@@ -926,11 +1306,133 @@ pub trait Repo: Profile {
         Ok(())
     }
 
-    fn seed(&self, rid: identity::RepoId) -> Result<(), Error> {
+    /// Seed `rid` from the peers in `scope`, or in the scope of the default
+    /// seeding policy if none is given. Seeding a repository that is already
+    /// seeded changes the scope of its policy.
+    fn seed(&self, rid: identity::RepoId, scope: Option<repo::Scope>) -> Result<(), Error> {
+        let profile = self.profile();
+        let scope = seeding_scope(scope, profile.config.node.seeding_policy);
+        let mut node = radicle::Node::new(profile.home().socket_from_env());
+
+        profile.seed(rid, scope, &mut node)?;
+
+        Ok(())
+    }
+
+    /// Every repository we seed, with its scope and whether it's in storage.
+    fn list_seed_policies(&self) -> Result<Vec<repo::SeedPolicy>, Error> {
+        let profile = &self.profile();
+        let storage = &profile.storage;
+        let policies = profile.policies()?;
+        let mut entries = Vec::new();
+
+        for policy in policies.seed_policies()? {
+            let Ok(policy) = policy else { continue };
+            let Some(scope) = policy.scope() else {
+                continue;
+            };
+            let replicated = storage.contains(&policy.rid).unwrap_or(false);
+            let name = replicated
+                .then(|| storage.repository(policy.rid).ok())
+                .flatten()
+                .and_then(|repo| repo.identity_doc().ok())
+                .and_then(|DocAt { doc, .. }| doc.project().ok())
+                .map(|project| project.name().to_owned());
+
+            entries.push(repo::SeedPolicy {
+                rid: policy.rid,
+                name,
+                scope: scope.into(),
+                replicated,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Where every seed we know of stands with our signed refs of `rid`.
+    fn sync_status(&self, rid: identity::RepoId) -> Result<Vec<cobs::repo::SeedSync>, Error> {
+        let profile = self.profile();
+        let repo = profile.storage.repository(rid)?;
+        let aliases = profile.aliases();
+        let db = profile.database()?;
+        let sigrefs = format!("refs/namespaces/{}/refs/rad/sigrefs", profile.public_key);
+        let local = match ref_oid(&repo, &sigrefs, false) {
+            Some(_) => Some(node::SyncedAt::load(&repo, profile.public_key)?),
+            None => None,
+        };
+
+        let mut seeds = Vec::new();
+        for seed in db.seeds_for(&rid)? {
+            let seed = seed?;
+            let status = match local {
+                Some(local) if seed.synced_at.oid == local.oid => {
+                    node::SyncStatus::Synced { at: local }.into()
+                }
+                Some(local) => node::SyncStatus::OutOfSync {
+                    local,
+                    remote: seed.synced_at,
+                }
+                .into(),
+                None => cobs::repo::SyncStatus::Unpublished {
+                    remote: seed.synced_at.into(),
+                },
+            };
+            seeds.push(cobs::repo::SeedSync {
+                nid: seed.nid,
+                alias: aliases.alias(&seed.nid),
+                status,
+            });
+        }
+
+        Ok(seeds)
+    }
+
+    /// Fetch `rid` from the connected seeds that have it, then announce our
+    /// refs and wait a while for seeds to sync them. Progress is reported to
+    /// `on_event` as it happens.
+    fn sync_repo(
+        &self,
+        rid: identity::RepoId,
+        mut on_event: impl FnMut(cobs::repo::SyncEvent),
+    ) -> Result<(), Error> {
         let profile = self.profile();
         let mut node = radicle::Node::new(profile.home().socket_from_env());
+        let providers = profile.database()?.get(&rid)?;
+        let seeds = connected_peers(&node)?
+            .into_iter()
+            .filter(|nid| providers.contains(nid) && *nid != profile.public_key)
+            .collect::<BTreeSet<_>>();
+
+        for nid in &seeds {
+            on_event(fetch_event(*nid, node.fetch(rid, *nid, FETCH_TIMEOUT)));
+        }
 
-        profile.seed(rid, node::policy::Scope::All, &mut node)?;
+        // Subscribe first, so no acknowledgement is missed.
+        let events = node.subscribe(SYNC_TIMEOUT)?;
+        node.announce_refs_for(rid, [profile.public_key])?;
+        on_event(cobs::repo::SyncEvent::Announced);
+
+        let deadline = std::time::Instant::now() + SYNC_TIMEOUT;
+        let mut synced = BTreeSet::new();
+        for event in events {
+            // The stream errors out once it has been quiet for the timeout.
+            let Ok(event) = event else { break };
+            if let node::Event::RefsSynced {
+                remote,
+                rid: id,
+                at,
+            } = event
+                && id == rid
+                && synced.insert(remote)
+            {
+                on_event(cobs::repo::SyncEvent::Synced { nid: remote, at });
+            }
+            let all_synced = !seeds.is_empty() && synced.is_superset(&seeds);
+            if all_synced || std::time::Instant::now() > deadline {
+                break;
+            }
+        }
 
         Ok(())
     }
@@ -948,4 +1450,516 @@ pub trait Repo: Profile {
 
         Ok(entries)
     }
+
+    /// Disk space taken by every repository in storage. Per-remote sizes
+    /// are left out, see [`Repo::repo_disk_usage`].
+    fn disk_usage(&self) -> Result<repo::DiskUsage, Error> {
+        let profile = self.profile();
+        let mut repos = Vec::new();
+
+        for RepositoryInfo { rid, doc, .. } in profile.storage.repositories()? {
+            let name = doc.project().ok().map(|project| project.name().to_owned());
+            repos.push(usage_or_error(&profile, rid, name));
+        }
+        repos.sort_by_key(|usage| std::cmp::Reverse(usage.total));
+
+        Ok(repo::DiskUsage {
+            total: repos.iter().map(|usage| usage.total).sum(),
+            repos,
+        })
+    }
+
+    /// Disk space taken by `rid`, with what each remote's refs reach.
+    fn repo_disk_usage(&self, rid: identity::RepoId) -> Result<repo::RepoUsage, Error> {
+        let profile = self.profile();
+        let repo = profile.storage.repository(rid)?;
+
+        repo_usage(&profile, rid, project_name(&repo), true)
+    }
+
+    /// Stop seeding `rid` and delete it from storage, the COB cache and the
+    /// node's notifications, and forget the states set on its notifications
+    /// in `inbox`.
+    fn remove_repo(&self, inbox: &impl InboxService, rid: identity::RepoId) -> Result<(), Error> {
+        let profile = self.profile();
+        let mut node = radicle::Node::new(profile.home().socket_from_env());
+
+        // Unseed first, so the node doesn't fetch it right back.
+        profile.unseed(rid, &mut node)?;
+        std::fs::remove_dir_all(profile.storage.path_of(&rid))?;
+
+        let cache = sqlite::Connection::open(profile.home().cobs().join(COBS_DB_FILE))?;
+        for table in ["issues", "patches"] {
+            let mut stmt = cache.prepare(format!("DELETE FROM {table} WHERE repo = ?1"))?;
+            stmt.bind((1, &rid))?;
+            stmt.next()?;
+        }
+        // Marking them unread drops their rows from the app's database. This
+        // looks the notifications up, so it goes before clearing them.
+        inbox.mark(
+            SetStatusNotifications::Repo(rid),
+            NotificationState::Unread,
+            None,
+        )?;
+        profile.notifications_mut()?.clear_by_repo(&rid)?;
+
+        Ok(())
+    }
+
+    /// Pack the repository's objects and drop unreachable ones with
+    /// `git gc --prune=now`.
+    fn gc_repo(&self, rid: identity::RepoId) -> Result<repo::RepoUsage, Error> {
+        let profile = self.profile();
+        let repo = profile.storage.repository(rid)?;
+        let name = project_name(&repo);
+
+        let mut command = std::process::Command::new("git");
+        command
+            .current_dir(repo.backend.path())
+            .arg("gc")
+            .arg("--prune=now")
+            .arg("--quiet");
+        #[cfg(windows)]
+        command.creation_flags(CREATE_NO_WINDOW);
+        let output = command.output()?;
+        if !output.status.success() {
+            return Err(std::io::Error::other(
+                String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            )
+            .into());
+        }
+
+        repo_usage(&profile, rid, name, false)
+    }
+
+    /// The repositories [`Repo::seeded_not_replicated`] lists, with the seeds
+    /// that could serve them and how fetching them last went.
+    fn pending_repos(&self, log: &repo::FetchLog) -> Result<Vec<repo::PendingRepo>, Error> {
+        let profile = self.profile();
+        let aliases = profile.aliases();
+        let db = profile.database()?;
+        let node = radicle::Node::new(profile.home().socket_from_env());
+        // Without a running node nothing is connected.
+        let connected = connected_peers(&node).unwrap_or_default();
+
+        self.seeded_not_replicated()?
+            .into_iter()
+            .map(|rid| {
+                let mut providers = db
+                    .get(&rid)?
+                    .into_iter()
+                    .map(|nid| repo::Provider {
+                        alias: aliases.alias(&nid),
+                        connected: connected.contains(&nid),
+                        nid,
+                    })
+                    .collect::<Vec<_>>();
+                providers.sort_by_key(|provider| (!provider.connected, provider.nid));
+
+                Ok(repo::PendingRepo {
+                    rid,
+                    providers,
+                    last_attempt: log.get(&rid),
+                })
+            })
+            .collect()
+    }
+
+    /// Try to fetch `rid` from the seeds that announced it, connected ones
+    /// first, until one succeeds. The outcome is recorded in `log`.
+    fn fetch_repo(
+        &self,
+        rid: identity::RepoId,
+        log: &repo::FetchLog,
+        mut on_event: impl FnMut(cobs::repo::SyncEvent),
+    ) -> Result<repo::FetchAttempt, Error> {
+        let profile = self.profile();
+        let mut node = radicle::Node::new(profile.home().socket_from_env());
+        let connected = match connected_peers(&node) {
+            Ok(connected) => connected,
+            Err(e) => {
+                log.record(
+                    rid,
+                    repo::FetchOutcome::Failed {
+                        reason: e.to_string(),
+                    },
+                );
+                return Err(e.into());
+            }
+        };
+        let mut providers = profile
+            .database()?
+            .get(&rid)?
+            .into_iter()
+            .filter(|nid| *nid != profile.public_key)
+            .collect::<Vec<_>>();
+        providers.sort_by_key(|nid| (!connected.contains(nid), *nid));
+
+        let mut outcome = repo::FetchOutcome::NoProviders;
+        for nid in providers {
+            let event = fetch_event(nid, node.fetch(rid, nid, FETCH_TIMEOUT));
+            let fetched = match &event {
+                cobs::repo::SyncEvent::FetchFailed { reason, .. } => {
+                    outcome = repo::FetchOutcome::Failed {
+                        reason: reason.clone(),
+                    };
+                    false
+                }
+                _ => {
+                    outcome = repo::FetchOutcome::Fetched { nid };
+                    true
+                }
+            };
+            on_event(event);
+            if fetched {
+                break;
+            }
+        }
+
+        Ok(log.record(rid, outcome))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod test {
+    use std::str::FromStr as _;
+
+    use radicle::identity::RepoId;
+    use radicle::node;
+    use radicle::node::NOTIFICATIONS_DB_FILE;
+    use radicle::node::config::DefaultSeedingPolicy;
+    use radicle::node::seed::Store as _;
+    use radicle::storage::ReadStorage as _;
+
+    use crate::cobs::Cursor;
+    use crate::cobs::repo::SyncStatus;
+    use crate::domain::inbox::models::notification::{NotificationState, SetStatusNotifications};
+    use crate::domain::inbox::service::Service as InboxService;
+    use crate::domain::inbox::traits::InboxService as _;
+    use crate::outbound::inbox::InboxStore;
+    use crate::outbound::sqlite::Sqlite;
+    use crate::repo::{FetchLog, FetchOutcome, InitRepo, Quorum, Scope, Visibility};
+    use crate::traits::Profile as _;
+    use crate::traits::repo::{Repo, quorum, usage_or_error};
+    use crate::{AppState, test};
+
+    const OID: &str = "e7a8b5e4ab6c2a4e1b7b3a1ec9b8f5a6d2c1b0a9";
+
+    #[test]
+    fn init_repo() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profile = test::profile(&tmp.path().join("home"), [0xff; 32]);
+        let (working, _) = radicle::test::fixtures::repository(tmp.path().join("working"));
+        let state = AppState::new(profile);
+
+        let rid = state
+            .init_repo(InitRepo {
+                path: tmp.path().join("working"),
+                name: "acme".to_string(),
+                description: "Acme's repository".to_string(),
+                default_branch: "master".to_string(),
+                visibility: Visibility::Public,
+                seed: false,
+                scope: None,
+            })
+            .unwrap();
+
+        assert!(state.profile().storage.contains(&rid).unwrap());
+        assert!(working.find_remote("rad").is_ok());
+    }
+
+    #[test]
+    fn init_repo_scope() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut profile = test::profile(&tmp.path().join("home"), [0xff; 32]);
+        profile.config.node.seeding_policy = DefaultSeedingPolicy::Allow {
+            scope: node::policy::Scope::Followed,
+        };
+        let state = AppState::new(profile);
+        let init = |name: &str, scope: Option<Scope>| {
+            let path = tmp.path().join(name);
+            radicle::test::fixtures::repository(&path);
+            state
+                .init_repo(InitRepo {
+                    path,
+                    name: name.to_string(),
+                    description: String::new(),
+                    default_branch: "master".to_string(),
+                    visibility: Visibility::Public,
+                    seed: true,
+                    scope,
+                })
+                .unwrap()
+        };
+        let scope_of = |rid| {
+            state
+                .list_seed_policies()
+                .unwrap()
+                .into_iter()
+                .find(|policy| policy.rid == rid)
+                .unwrap()
+                .scope
+        };
+
+        // Without a scope, the default seeding policy's is used.
+        let rid = init("acme", None);
+        assert_eq!(scope_of(rid), Scope::Followed);
+
+        let rid = init("heartwood", Some(Scope::All));
+        assert_eq!(scope_of(rid), Scope::All);
+
+        // Seeding again applies the same fallback.
+        state.seed(rid, None).unwrap();
+        assert_eq!(scope_of(rid), Scope::Followed);
+        state.seed(rid, Some(Scope::All)).unwrap();
+        assert_eq!(scope_of(rid), Scope::All);
+    }
+
+    #[test]
+    fn commits_after_cursor() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, head) = test::repo(tmp.path());
+        let head = Some(head);
+        let all = state
+            .list_repo_commits(rid, head, None, None, None, None, None)
+            .unwrap()
+            .content;
+
+        let first = state
+            .list_repo_commits(rid, head, None, None, None, None, Some(1))
+            .unwrap();
+        let second = state
+            .list_repo_commits(rid, head, None, None, None, first.next.clone(), Some(1))
+            .unwrap();
+        assert_eq!(first.content[0].id, all[0].id);
+        assert_eq!(second.content[0].id, all[1].id);
+
+        // A cursor on a commit that is gone resumes from its commit time.
+        let gone = Cursor {
+            key: all[0].committer.time.seconds(),
+            id: "0000000000000000000000000000000000000001".to_string(),
+        };
+        let resumed = state
+            .list_repo_commits(rid, head, None, None, None, Some(gone.clone()), None)
+            .unwrap();
+        assert_eq!(
+            resumed.content.iter().map(|c| c.id).collect::<Vec<_>>(),
+            all.iter()
+                .filter(|c| c.committer.time.seconds() < gone.key)
+                .map(|c| c.id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn sync_status() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, _) = test::repo(tmp.path());
+        let public_key = state.profile().public_key;
+        let repo = state.profile().storage.repository(rid).unwrap();
+        let local = radicle::node::SyncedAt::load(&repo, public_key).unwrap();
+        let synced = |at: radicle::git::Oid| {
+            let profile = state.profile();
+            profile
+                .home
+                .database_mut(profile.config.node.database)
+                .unwrap()
+                .synced(
+                    &rid,
+                    &public_key,
+                    at,
+                    radicle::node::Timestamp::try_from(test::TIMESTAMP).unwrap(),
+                )
+                .unwrap();
+        };
+
+        synced(local.oid);
+        let seeds = state.sync_status(rid).unwrap();
+        assert_eq!(seeds.len(), 1);
+        assert_eq!(seeds[0].nid, public_key);
+        assert!(matches!(seeds[0].status, SyncStatus::Synced { .. }));
+
+        let stale =
+            radicle::git::Oid::from_str("0000000000000000000000000000000000000001").unwrap();
+        synced(stale);
+        assert!(matches!(
+            state.sync_status(rid).unwrap()[0].status,
+            SyncStatus::OutOfSync { remote, .. } if remote.oid == stale
+        ));
+
+        // Only seeding a repository leaves us without signed refs of our own.
+        repo.backend
+            .find_reference(&format!("refs/namespaces/{public_key}/refs/rad/sigrefs"))
+            .unwrap()
+            .delete()
+            .unwrap();
+        assert!(matches!(
+            state.sync_status(rid).unwrap()[0].status,
+            SyncStatus::Unpublished { remote } if remote.oid == stale
+        ));
+    }
+
+    #[test]
+    fn canonical_rules() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, head) = test::repo(tmp.path());
+
+        let rules = state.canonical_rules(rid).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].refs.len(), 1);
+
+        let master = &rules[0].refs[0];
+        assert_eq!(master.name, "refs/heads/master");
+        assert_eq!(master.votes.len(), 1);
+        assert_eq!(master.quorum, Quorum::Reached { oid: head });
+    }
+
+    #[test]
+    fn quorum_of_diverging_heads() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, head) = test::repo(tmp.path());
+        let repo = state.profile().storage.repository(rid).unwrap();
+        let parent = repo.backend.find_commit(head.into()).unwrap();
+        let tree = parent.tree().unwrap();
+        let sig = radicle::git::raw::Signature::now("acme", "acme@example.com").unwrap();
+        let commit = |message: &str| -> radicle::git::Oid {
+            repo.backend
+                .commit(None, &sig, &sig, message, &tree, &[&parent])
+                .unwrap()
+                .into()
+        };
+        let (left, right) = (commit("Left"), commit("Right"));
+
+        // A vote for a commit also counts for the commits it descends from.
+        assert_eq!(
+            quorum(&repo, &[left, head], 2, true),
+            Quorum::Reached { oid: head }
+        );
+        assert_eq!(
+            quorum(&repo, &[left, head], 1, true),
+            Quorum::Reached { oid: left }
+        );
+        assert_eq!(quorum(&repo, &[left, right], 2, true), Quorum::NotReached);
+        assert_eq!(quorum(&repo, &[head], 2, true), Quorum::NotReached);
+
+        let mut candidates = vec![left, right];
+        candidates.sort();
+        assert_eq!(
+            quorum(&repo, &[left, right], 1, true),
+            Quorum::Diverged { candidates }
+        );
+    }
+
+    #[test]
+    fn pending_repos() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profile = test::profile(tmp.path(), [0xff; 32]);
+        let rid = "rad:z3gqcJUoA1n9HaHKufZs5FCSGazv5".parse().unwrap();
+        profile
+            .policies_mut()
+            .unwrap()
+            .seed(&rid, radicle::node::policy::Scope::All)
+            .unwrap();
+        let state = AppState::new(profile);
+        let log = FetchLog::default();
+
+        let pending = state.pending_repos(&log).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].rid, rid);
+        assert!(pending[0].providers.is_empty());
+        assert!(pending[0].last_attempt.is_none());
+
+        // There is no node to fetch with, which is recorded as well.
+        assert!(state.fetch_repo(rid, &log, |_| {}).is_err());
+        let attempt = state.pending_repos(&log).unwrap()[0].last_attempt.clone();
+        assert!(matches!(
+            attempt.unwrap().outcome,
+            FetchOutcome::Failed { .. }
+        ));
+    }
+
+    #[test]
+    fn disk_usage_and_removal() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (state, rid, _) = test::repo(tmp.path());
+        let public_key = state.profile().public_key;
+
+        let usage = state.disk_usage().unwrap();
+        assert_eq!(usage.repos.len(), 1);
+        let repo = &usage.repos[0];
+        assert_eq!(repo.name.as_deref(), Some("acme"));
+        assert_eq!(usage.total, repo.total);
+        assert!(repo.total >= repo.loose_objects + repo.packs);
+        assert_eq!(repo.remotes.len(), 1);
+        assert_eq!(repo.remotes[0].nid, public_key);
+        assert!(repo.remotes[0].refs > 0);
+        assert!(repo.remotes[0].size.is_none());
+        assert!(repo.error.is_none());
+
+        let repo = state.repo_disk_usage(rid).unwrap();
+        assert_eq!(repo.name.as_deref(), Some("acme"));
+        let size = repo.remotes[0].size.unwrap();
+        assert!(size > 0);
+        // The only remote has every object to itself.
+        assert_eq!(repo.remotes[0].unique, Some(size));
+
+        // Opening the node's notifications store creates its tables.
+        let notifications = state.profile().node().join(NOTIFICATIONS_DB_FILE);
+        drop(state.profile().notifications_mut().unwrap());
+        sqlite::Connection::open(&notifications)
+            .unwrap()
+            .execute(format!(
+                "INSERT INTO 'repository-notifications' (repo, ref, old, new, timestamp)
+                 VALUES ('{rid}', 'refs/namespaces/{public_key}/refs/cobs/xyz.radicle.issue/{OID}', NULL, '{OID}', 1700000000000)"
+            ))
+            .unwrap();
+        let inbox = InboxService::new(
+            InboxStore::open(
+                tmp.path().join("inbox.db"),
+                Sqlite::reader(&notifications).unwrap(),
+            )
+            .unwrap(),
+        );
+        inbox
+            .mark(
+                SetStatusNotifications::Repo(rid),
+                NotificationState::Read,
+                None,
+            )
+            .unwrap();
+        assert_eq!(inbox.notification_count().unwrap(), 0);
+
+        state.remove_repo(&inbox, rid).unwrap();
+        assert!(!state.profile().storage.contains(&rid).unwrap());
+        assert!(state.disk_usage().unwrap().repos.is_empty());
+        assert_eq!(inbox.notification_count().unwrap(), 0);
+        // Both the node's notification and the state set on it are gone.
+        let rows = |db: &std::path::Path, table: &str| {
+            sqlite::Connection::open(db)
+                .unwrap()
+                .prepare(format!("SELECT id FROM '{table}'"))
+                .unwrap()
+                .into_iter()
+                .count()
+        };
+        assert_eq!(rows(&notifications, "repository-notifications"), 0);
+        assert_eq!(rows(&tmp.path().join("inbox.db"), "states"), 0);
+    }
+
+    #[test]
+    fn disk_usage_of_unreadable_repo() {
+        let tmp = tempfile::tempdir().unwrap();
+        let profile = test::profile(&tmp.path().join("home"), [0xff; 32]);
+        let rid = "rad:z3gqcJUoA1n9HaHKufZs5FCSGazv5"
+            .parse::<RepoId>()
+            .unwrap();
+
+        let usage = usage_or_error(&profile, rid, Some("acme".to_string()));
+        assert_eq!(usage.rid, rid);
+        assert_eq!(usage.name.as_deref(), Some("acme"));
+        assert_eq!(usage.total, 0);
+        assert!(usage.remotes.is_empty());
+        assert!(usage.error.is_some());
+    }
 }
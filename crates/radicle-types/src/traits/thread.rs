@@ -101,7 +101,7 @@ pub trait Thread: Profile {
         let profile = self.profile();
         let aliases = &profile.aliases();
         let mut node = Node::new(profile.home().socket_from_env());
-        let signer = profile.signer()?;
+        let signer = self.signer()?;
         let repo = profile.storage.repository(rid)?;
         let mut issues = profile.issues_mut(&repo, &signer)?;
         let mut issue = issues.get_mut(&new.id.into())?;
@@ -145,7 +145,7 @@ pub trait Thread: Profile {
         let profile = self.profile();
         let aliases = &profile.aliases();
         let mut node = Node::new(profile.home().socket_from_env());
-        let signer = profile.signer()?;
+        let signer = self.signer()?;
         let repo = profile.storage.repository(rid)?;
         let mut patches = profile.patches_mut(&repo, &signer)?;
         let mut patch = patches.get_mut(&new.id.into())?;
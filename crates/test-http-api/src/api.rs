@@ -1,6 +1,5 @@
-use std::ops::Deref;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock};
 
 use axum::Router;
 use axum::extract::State;
@@ -11,7 +10,8 @@ use hyper::header::CONTENT_TYPE;
 use serde::{Deserialize, Serialize};
 use tower_http::cors::{self, CorsLayer};
 
-use radicle::node::NodeId;
+use radicle::crypto::ssh::Passphrase;
+use radicle::node::{Alias, NodeId};
 use radicle::storage::{ReadRepository as _, ReadStorage as _};
 use radicle::{git, identity};
 use radicle_types as types;
@@ -20,52 +20,89 @@ use radicle_types::cobs::FromRadicleAction;
 use radicle_types::cobs::issue;
 use radicle_types::cobs::issue::NewIssue;
 use radicle_types::config::Version;
+use radicle_types::domain::inbox::models::notification;
+use radicle_types::domain::inbox::service::Service as InboxService;
+use radicle_types::domain::inbox::traits::InboxService as _;
 use radicle_types::domain::issue::service::Service as IssueService;
 use radicle_types::domain::issue::traits::IssueService as _;
 use radicle_types::domain::patch::models;
 use radicle_types::domain::patch::service::Service;
 use radicle_types::domain::patch::traits::PatchService;
+use radicle_types::domain::search::models::search;
+use radicle_types::domain::search::service::Service as SearchService;
+use radicle_types::domain::search::traits::SearchService as _;
 use radicle_types::error::Error;
+use radicle_types::outbound::inbox::InboxStore;
+use radicle_types::outbound::search::SearchIndex;
 use radicle_types::outbound::sqlite::Sqlite;
-use radicle_types::traits::Profile;
 use radicle_types::traits::cobs::Cobs;
+use radicle_types::traits::diagnostics::Diagnostics;
+use radicle_types::traits::identity::{Identities, IdentitiesMut};
+use radicle_types::traits::inbox::Inbox;
 use radicle_types::traits::issue::{Issues, IssuesMut};
 use radicle_types::traits::job::Jobs;
+use radicle_types::traits::keys::Keys;
+use radicle_types::traits::node::NodeInfo;
 use radicle_types::traits::patch::{Patches, PatchesMut};
+use radicle_types::traits::policy::Policies;
 use radicle_types::traits::repo::{Repo, Show};
 use radicle_types::traits::thread::Thread;
+use radicle_types::traits::{Profile, ProfileMut};
 
 #[derive(Clone)]
 pub struct Context {
-    profile: Arc<radicle::Profile>,
+    profile: Arc<RwLock<radicle::Profile>>,
     patches: Arc<Service<Sqlite>>,
     issues: Arc<IssueService<Sqlite>>,
+    inbox: Arc<InboxService<InboxStore>>,
+    search: Arc<SearchService<SearchIndex>>,
+    fetches: types::repo::FetchLog,
 }
 
 impl Repo for Context {}
 impl Cobs for Context {}
+impl Diagnostics for Context {}
+impl Identities for Context {}
+impl IdentitiesMut for Context {}
+impl Inbox for Context {}
 impl Thread for Context {}
 impl Issues for Context {}
 impl IssuesMut for Context {}
 impl Jobs for Context {}
+impl Keys for Context {}
+impl NodeInfo for Context {}
 impl Patches for Context {}
 impl PatchesMut for Context {}
+impl Policies for Context {}
 impl Profile for Context {
     fn profile(&self) -> radicle::Profile {
-        self.profile.deref().clone()
+        self.profile
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+impl ProfileMut for Context {
+    fn modify_profile<T>(&self, f: impl FnOnce(&mut radicle::Profile) -> T) -> T {
+        f(&mut self.profile.write().unwrap_or_else(PoisonError::into_inner))
     }
 }
 
 impl Context {
     pub fn new(
-        profile: Arc<radicle::Profile>,
+        profile: radicle::Profile,
         patches: Arc<Service<Sqlite>>,
         issues: Arc<IssueService<Sqlite>>,
+        inbox: Arc<InboxService<InboxStore>>,
+        search: Arc<SearchService<SearchIndex>>,
     ) -> Self {
         Self {
-            profile,
+            profile: Arc::new(RwLock::new(profile)),
             patches,
             issues,
+            inbox,
+            search,
+            fetches: types::repo::FetchLog::default(),
         }
     }
 }
@@ -73,17 +110,44 @@ impl Context {
 pub fn router(ctx: Context) -> Router {
     Router::new()
         .route("/config", post(config_handler))
+        .route("/update_config", post(update_config_handler))
         .route("/authenticate", post(auth_handler))
+        .route("/lock", post(auth_handler))
+        .route("/set_unlock_timeout", post(auth_handler))
+        .route("/unlock_timeout", post(unlock_timeout_handler))
+        .route("/key_info", post(key_info_handler))
+        .route("/export_public_key", post(export_public_key_handler))
+        .route("/change_passphrase", post(change_passphrase_handler))
         .route("/repo_count", post(repo_count_handler))
         .route("/list_repos", post(repo_root_handler))
         .route("/list_repos_summary", post(list_repos_summary_handler))
+        .route("/list_seed_policies", post(list_seed_policies_handler))
         .route(
             "/seeded_not_replicated",
             post(seeded_not_replicated_handler),
         )
+        .route("/list_follow_policies", post(list_follow_policies_handler))
+        .route("/follow", post(follow_handler))
+        .route("/unfollow", post(unfollow_handler))
+        .route("/block_node", post(block_node_handler))
+        .route("/block_repo", post(block_repo_handler))
+        .route("/pending_repos", post(pending_repos_handler))
+        .route("/fetch_repo", post(fetch_repo_handler))
+        .route("/disk_usage", post(disk_usage_handler))
+        .route("/repo_disk_usage", post(repo_disk_usage_handler))
+        .route("/remove_repo", post(remove_repo_handler))
+        .route("/gc_repo", post(gc_repo_handler))
+        .route("/check_storage", post(check_storage_handler))
+        .route("/repair_cache", post(repair_cache_handler))
+        .route("/repair_repo", post(repair_repo_handler))
+        .route("/sync_status", post(sync_status_handler))
+        .route("/sync_repo", post(sync_repo_handler))
         .route("/repo_by_id", post(repo_handler))
+        .route("/init_repo", post(init_repo_handler))
         .route("/list_repo_refs", post(list_repo_refs_handler))
+        .route("/canonical_rules", post(canonical_rules_handler))
         .route("/version", post(version_handler))
+        .route("/node_status", post(node_status_handler))
         .route("/diff_stats", post(diff_stats_handler))
         .route(
             "/activity_by_issue",
@@ -108,12 +172,19 @@ pub fn router(ctx: Context) -> Router {
         .route("/create_issue_comment", post(create_issue_comment_handler))
         .route("/edit_issue", post(edit_issue_handler))
         .route("/issue_by_id", post(issue_handler))
+        .route("/issue_counts", post(issue_counts_handler))
         .route("/comment_threads_by_issue_id", post(issue_threads_handler))
         .route("/list_patches", post(patches_handler))
+        .route("/create_patch", post(create_patch_handler))
         .route("/patch_by_id", post(patch_handler))
+        .route("/patch_counts", post(patch_counts_handler))
         .route("/revisions_by_patch", post(revision_handler))
         .route("/edit_patch", post(edit_patch_handler))
         .route("/create_patch_review", post(create_patch_review_handler))
+        .route(
+            "/create_patch_revision",
+            post(create_patch_revision_handler),
+        )
         .route("/delete_patch", post(delete_patch_handler))
         .route("/get_embed", post(get_embeds_handler))
         .route("/save_embed_by_path", post(save_embed_handler))
@@ -121,9 +192,18 @@ pub fn router(ctx: Context) -> Router {
         .route("/save_embed_by_bytes", post(save_embed_handler))
         .route("/save_embed_to_disk", post(save_embed_handler))
         .route("/list_jobs", post(jobs_handler))
+        .route("/list_identity_revisions", post(identity_revisions_handler))
+        .route("/identity_revision_diff", post(identity_diff_handler))
+        .route(
+            "/propose_identity_revision",
+            post(propose_identity_revision_handler),
+        )
+        .route("/edit_identity", post(edit_identity_handler))
+        .route("/search_cobs", post(search_cobs_handler))
         .route("/list_notifications", post(list_notifications_handler))
         .route("/notification_count", post(notification_count_handler))
         .route("/clear_notifications", post(clear_notifications_handler))
+        .route("/mark_notifications", post(mark_notifications_handler))
         .layer(
             CorsLayer::new()
                 .allow_origin(cors::Any)
@@ -139,6 +219,21 @@ async fn config_handler(State(ctx): State<Context>) -> impl IntoResponse {
     Ok::<_, Error>(Json(config))
 }
 
+// Deserialize only: `ConfigEdit` is an inbound type and isn't Serialize.
+#[derive(Deserialize)]
+struct UpdateConfigBody {
+    pub edit: types::config::ConfigEdit,
+}
+
+async fn update_config_handler(
+    State(ctx): State<Context>,
+    Json(UpdateConfigBody { edit }): Json<UpdateConfigBody>,
+) -> impl IntoResponse {
+    let update = ctx.update_config(edit)?;
+
+    Ok::<_, Error>(Json(update))
+}
+
 async fn auth_handler() -> impl IntoResponse {
     Ok::<_, Error>(Json(()))
 }
@@ -157,6 +252,35 @@ async fn repo_root_handler(
     Ok::<_, Error>(Json(repos))
 }
 
+async fn unlock_timeout_handler() -> impl IntoResponse {
+    Json(types::keys::KeyCache::DEFAULT_TIMEOUT.as_secs())
+}
+
+async fn key_info_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let info = ctx.key_info()?;
+
+    Ok::<_, Error>(Json(info))
+}
+
+async fn export_public_key_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    Json(ctx.export_public_key())
+}
+
+#[derive(Deserialize)]
+struct ChangePassphraseBody {
+    pub current: Option<Passphrase>,
+    pub new: Passphrase,
+}
+
+async fn change_passphrase_handler(
+    State(ctx): State<Context>,
+    Json(ChangePassphraseBody { current, new }): Json<ChangePassphraseBody>,
+) -> impl IntoResponse {
+    ctx.change_passphrase(current, new)?;
+
+    Ok::<_, Error>(Json(()))
+}
+
 async fn repo_count_handler(State(ctx): State<Context>) -> impl IntoResponse {
     let repos = ctx.repo_count()?;
     Ok::<_, Error>(Json(repos))
@@ -167,22 +291,120 @@ async fn list_repos_summary_handler(State(ctx): State<Context>) -> impl IntoResp
     Ok::<_, Error>(Json(repos))
 }
 
+async fn list_seed_policies_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let policies = ctx.list_seed_policies()?;
+    Ok::<_, Error>(Json(policies))
+}
+
 async fn seeded_not_replicated_handler(State(ctx): State<Context>) -> impl IntoResponse {
     let rids = ctx.seeded_not_replicated()?;
     Ok::<_, Error>(Json(rids))
 }
 
-async fn list_notifications_handler() -> impl IntoResponse {
-    Ok::<_, Error>(Json(Vec::<
-        radicle_types::domain::inbox::models::notification::NotificationsByRepo,
-    >::new()))
+async fn list_follow_policies_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let policies = ctx.list_follow_policies()?;
+    Ok::<_, Error>(Json(policies))
 }
 
-async fn notification_count_handler() -> impl IntoResponse {
-    Ok::<_, Error>(Json(0_usize))
+#[derive(Serialize, Deserialize)]
+struct FollowBody {
+    pub nid: NodeId,
+    pub alias: Option<Alias>,
+}
+
+async fn follow_handler(
+    State(ctx): State<Context>,
+    Json(FollowBody { nid, alias }): Json<FollowBody>,
+) -> impl IntoResponse {
+    let updated = ctx.follow(nid, alias)?;
+
+    Ok::<_, Error>(Json(updated))
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeBody {
+    pub nid: NodeId,
+}
+
+async fn unfollow_handler(
+    State(ctx): State<Context>,
+    Json(NodeBody { nid }): Json<NodeBody>,
+) -> impl IntoResponse {
+    let updated = ctx.unfollow(nid)?;
+
+    Ok::<_, Error>(Json(updated))
+}
+
+async fn block_node_handler(
+    State(ctx): State<Context>,
+    Json(NodeBody { nid }): Json<NodeBody>,
+) -> impl IntoResponse {
+    let updated = ctx.block_node(nid)?;
+
+    Ok::<_, Error>(Json(updated))
+}
+
+async fn block_repo_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    let updated = ctx.block_repo(rid)?;
+
+    Ok::<_, Error>(Json(updated))
+}
+
+// Deserialize only: `RepoGroupParams` is an inbound type and isn't Serialize.
+#[derive(Deserialize)]
+struct ListNotificationsBody {
+    pub params: notification::RepoGroupParams,
+}
+
+async fn list_notifications_handler(
+    State(ctx): State<Context>,
+    Json(ListNotificationsBody { params }): Json<ListNotificationsBody>,
+) -> impl IntoResponse {
+    let notifications = ctx.list_notifications(ctx.inbox.as_ref(), params)?;
+
+    Ok::<_, Error>(Json(notifications))
 }
 
-async fn clear_notifications_handler() -> impl IntoResponse {
+async fn notification_count_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let count = ctx.inbox.notification_count()?;
+
+    Ok::<_, Error>(Json(count))
+}
+
+#[derive(Serialize, Deserialize)]
+struct ClearNotificationsBody {
+    pub params: notification::SetStatusNotifications,
+}
+
+async fn clear_notifications_handler(
+    State(ctx): State<Context>,
+    Json(ClearNotificationsBody { params }): Json<ClearNotificationsBody>,
+) -> impl IntoResponse {
+    ctx.clear_notifications(params)?;
+
+    Ok::<_, Error>(Json(()))
+}
+
+#[derive(Serialize, Deserialize)]
+struct MarkNotificationsBody {
+    pub params: notification::SetStatusNotifications,
+    pub state: notification::NotificationState,
+    pub until: Option<u64>,
+}
+
+async fn mark_notifications_handler(
+    State(ctx): State<Context>,
+    Json(MarkNotificationsBody {
+        params,
+        state,
+        until,
+    }): Json<MarkNotificationsBody>,
+) -> impl IntoResponse {
+    ctx.inbox.mark(params, state, until)?;
+
     Ok::<_, Error>(Json(()))
 }
 
@@ -200,6 +422,121 @@ async fn repo_handler(
     Ok::<_, Error>(Json(info))
 }
 
+async fn pending_repos_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let repos = ctx.pending_repos(&ctx.fetches)?;
+    Ok::<_, Error>(Json(repos))
+}
+
+#[derive(Serialize)]
+struct FetchRepoResponse {
+    attempt: types::repo::FetchAttempt,
+    events: Vec<types::cobs::repo::SyncEvent>,
+}
+
+/// Like `/sync_repo`, responds with the progress events once done.
+async fn fetch_repo_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    let mut events = Vec::new();
+    let attempt = ctx.fetch_repo(rid, &ctx.fetches, |event| events.push(event))?;
+
+    Ok::<_, Error>(Json(FetchRepoResponse { attempt, events }))
+}
+
+async fn disk_usage_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let usage = ctx.disk_usage()?;
+    Ok::<_, Error>(Json(usage))
+}
+
+async fn repo_disk_usage_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    let usage = ctx.repo_disk_usage(rid)?;
+
+    Ok::<_, Error>(Json(usage))
+}
+
+async fn remove_repo_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    ctx.remove_repo(ctx.inbox.as_ref(), rid)?;
+
+    Ok::<_, Error>(Json(()))
+}
+
+async fn gc_repo_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    let usage = ctx.gc_repo(rid)?;
+
+    Ok::<_, Error>(Json(usage))
+}
+
+async fn check_storage_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let report = ctx.check_storage()?;
+    Ok::<_, Error>(Json(report))
+}
+
+async fn repair_cache_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let report = ctx.repair_cache()?;
+    Ok::<_, Error>(Json(report))
+}
+
+#[derive(Serialize, Deserialize)]
+struct RepairRepoBody {
+    pub rid: identity::RepoId,
+    pub repair: types::diagnostics::Repair,
+}
+
+async fn repair_repo_handler(
+    State(ctx): State<Context>,
+    Json(RepairRepoBody { rid, repair }): Json<RepairRepoBody>,
+) -> impl IntoResponse {
+    let health = ctx.repair_repo(rid, repair, &ctx.fetches)?;
+
+    Ok::<_, Error>(Json(health))
+}
+
+async fn sync_status_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    let seeds = ctx.sync_status(rid)?;
+
+    Ok::<_, Error>(Json(seeds))
+}
+
+/// Responds with every progress event once syncing is done, there being no
+/// channel to stream them over.
+async fn sync_repo_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    let mut events = Vec::new();
+    ctx.sync_repo(rid, |event| events.push(event))?;
+
+    Ok::<_, Error>(Json(events))
+}
+
+// Deserialize only: `InitRepo` is an inbound type and isn't Serialize.
+#[derive(Deserialize)]
+struct InitRepoBody {
+    pub args: types::repo::InitRepo,
+}
+
+async fn init_repo_handler(
+    State(ctx): State<Context>,
+    Json(InitRepoBody { args }): Json<InitRepoBody>,
+) -> impl IntoResponse {
+    let rid = ctx.init_repo(args)?;
+
+    Ok::<_, Error>(Json(rid))
+}
+
 async fn list_repo_refs_handler(
     State(ctx): State<Context>,
     Json(RepoBody { rid }): Json<RepoBody>,
@@ -209,6 +546,15 @@ async fn list_repo_refs_handler(
     Ok::<_, Error>(Json(refs))
 }
 
+async fn canonical_rules_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    let rules = ctx.canonical_rules(rid)?;
+
+    Ok::<_, Error>(Json(rules))
+}
+
 async fn version_handler() -> impl IntoResponse {
     let version = Version {
         version: String::from("0.6.1"),
@@ -218,6 +564,12 @@ async fn version_handler() -> impl IntoResponse {
     Ok::<_, Error>(Json(version))
 }
 
+async fn node_status_handler(State(ctx): State<Context>) -> impl IntoResponse {
+    let status = ctx.node_status()?;
+
+    Ok::<_, Error>(Json(status))
+}
+
 #[derive(Serialize, Deserialize)]
 struct DiffStatsBody {
     pub rid: identity::RepoId,
@@ -393,6 +745,8 @@ struct ListRepoCommitsBody {
     #[serde(default)]
     pub revision: Option<String>,
     pub skip: Option<usize>,
+    #[serde(default)]
+    pub after: Option<types::cobs::Cursor>,
     pub take: Option<usize>,
 }
 
@@ -404,10 +758,11 @@ async fn list_repo_commits_handler(
         peer,
         revision,
         skip,
+        after,
         take,
     }): Json<ListRepoCommitsBody>,
 ) -> impl IntoResponse {
-    let commits = ctx.list_repo_commits(rid, head, peer, revision, skip, take)?;
+    let commits = ctx.list_repo_commits(rid, head, peer, revision, skip, after, take)?;
 
     Ok::<_, Error>(Json(commits))
 }
@@ -457,7 +812,9 @@ async fn repo_commit_handler(
 struct IssuesBody {
     pub rid: identity::RepoId,
     pub status: Option<types::cobs::query::IssueStatus>,
+    pub filter: Option<types::cobs::query::ListFilter>,
     pub skip: Option<usize>,
+    pub after: Option<types::cobs::Cursor>,
     pub take: Option<usize>,
 }
 
@@ -466,18 +823,76 @@ async fn issues_handler(
     Json(IssuesBody {
         rid,
         status,
+        filter,
         skip,
+        after,
         take,
     }): Json<IssuesBody>,
 ) -> impl IntoResponse {
-    let aliases = ctx.profile.aliases();
-    let page = ctx
-        .issues
-        .list_paginated(rid, status.unwrap_or_default(), skip, take, &aliases)?;
+    let aliases = ctx.profile().aliases();
+    let page = ctx.issues.list_paginated(
+        rid,
+        status.unwrap_or_default(),
+        &filter.unwrap_or_default(),
+        skip,
+        after,
+        take,
+        &aliases,
+    )?;
 
     Ok::<_, Error>(Json(page))
 }
 
+#[derive(Serialize, Deserialize)]
+struct SearchCobsBody {
+    pub query: String,
+    pub rids: Option<Vec<identity::RepoId>>,
+    pub kinds: Option<Vec<search::Kind>>,
+    pub author: Option<identity::Did>,
+    pub label: Option<radicle::cob::Label>,
+}
+
+async fn search_cobs_handler(
+    State(ctx): State<Context>,
+    Json(SearchCobsBody {
+        query,
+        rids,
+        kinds,
+        author,
+        label,
+    }): Json<SearchCobsBody>,
+) -> impl IntoResponse {
+    let aliases = ctx.profile().aliases();
+    let query = search::SearchQuery {
+        query,
+        rids,
+        kinds,
+        author,
+        label,
+    };
+    let hits = ctx.search.search_hits(&query, search::MAX_HITS, &aliases)?;
+
+    Ok::<_, Error>(Json(hits))
+}
+
+#[derive(Serialize, Deserialize)]
+struct IssueCountsBody {
+    pub rid: identity::RepoId,
+    pub status: Option<types::cobs::query::IssueStatus>,
+}
+
+async fn issue_counts_handler(
+    State(ctx): State<Context>,
+    Json(IssueCountsBody { rid, status }): Json<IssueCountsBody>,
+) -> impl IntoResponse {
+    let aliases = ctx.profile().aliases();
+    let counts = ctx
+        .issues
+        .faceted_counts(rid, status.unwrap_or_default(), &aliases)?;
+
+    Ok::<_, Error>(Json(counts))
+}
+
 #[derive(Serialize, Deserialize)]
 struct CreateIssuesBody {
     pub rid: identity::RepoId,
@@ -624,8 +1039,10 @@ async fn issue_threads_handler(
 struct PatchesBody {
     pub rid: identity::RepoId,
     pub skip: Option<usize>,
+    pub after: Option<types::cobs::Cursor>,
     pub take: Option<usize>,
     pub status: Option<types::cobs::query::PatchStatus>,
+    pub filter: Option<types::cobs::query::ListFilter>,
 }
 
 async fn patches_handler(
@@ -633,19 +1050,61 @@ async fn patches_handler(
     Json(PatchesBody {
         rid,
         skip,
+        after,
         take,
         status,
+        filter,
     }): Json<PatchesBody>,
 ) -> impl IntoResponse {
-    let aliases = ctx.profile.aliases();
-    let delegates = Vec::from(ctx.profile.storage.repository(rid)?.delegates()?);
-    let page = ctx
-        .patches
-        .list_paginated(rid, status, skip, take, &delegates, &aliases)?;
+    let aliases = ctx.profile().aliases();
+    let delegates = Vec::from(ctx.profile().storage.repository(rid)?.delegates()?);
+    let page = ctx.patches.list_paginated(
+        rid,
+        status,
+        &filter.unwrap_or_default(),
+        skip,
+        after,
+        take,
+        &delegates,
+        &aliases,
+    )?;
 
     Ok::<_, Error>(Json(page))
 }
 
+#[derive(Serialize, Deserialize)]
+struct PatchCountsBody {
+    pub rid: identity::RepoId,
+    pub status: Option<types::cobs::query::PatchStatus>,
+}
+
+async fn patch_counts_handler(
+    State(ctx): State<Context>,
+    Json(PatchCountsBody { rid, status }): Json<PatchCountsBody>,
+) -> impl IntoResponse {
+    let aliases = ctx.profile().aliases();
+    let counts = ctx.patches.faceted_counts(rid, status, &aliases)?;
+
+    Ok::<_, Error>(Json(counts))
+}
+
+// Deserialize only: `NewPatch` is an inbound type and isn't Serialize.
+#[derive(Deserialize)]
+struct CreatePatchBody {
+    pub rid: identity::RepoId,
+    pub new: models::patch::NewPatch,
+    pub opts: CobOptions,
+}
+
+async fn create_patch_handler(
+    State(ctx): State<Context>,
+    Json(CreatePatchBody { rid, new, opts }): Json<CreatePatchBody>,
+) -> impl IntoResponse {
+    let patch = ctx.create_patch(rid, new, opts)?;
+
+    Ok::<_, Error>(Json(patch))
+}
+
 #[derive(Serialize, Deserialize)]
 struct PatchBody {
     pub rid: identity::RepoId,
@@ -709,6 +1168,23 @@ async fn create_patch_review_handler(
     Ok::<_, Error>(Json(review_id))
 }
 
+// Deserialize only: `CreateRevisionArgs` is an inbound type and isn't Serialize.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatePatchRevisionBody {
+    pub args: models::patch::CreateRevisionArgs,
+    pub opts: CobOptions,
+}
+
+async fn create_patch_revision_handler(
+    State(ctx): State<Context>,
+    Json(CreatePatchRevisionBody { args, opts }): Json<CreatePatchRevisionBody>,
+) -> impl IntoResponse {
+    let patch = ctx.create_patch_revision(args, opts)?;
+
+    Ok::<_, Error>(Json(patch))
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct DeletePatchBody {
@@ -740,3 +1216,65 @@ async fn jobs_handler(
 
     Ok::<_, Error>(Json(jobs))
 }
+
+async fn identity_revisions_handler(
+    State(ctx): State<Context>,
+    Json(RepoBody { rid }): Json<RepoBody>,
+) -> impl IntoResponse {
+    let revisions = ctx.list_identity_revisions(rid)?;
+
+    Ok::<_, Error>(Json(revisions))
+}
+
+#[derive(Serialize, Deserialize)]
+struct IdentityDiffBody {
+    pub rid: identity::RepoId,
+    pub base: Option<git::Oid>,
+    pub head: git::Oid,
+}
+
+async fn identity_diff_handler(
+    State(ctx): State<Context>,
+    Json(IdentityDiffBody { rid, base, head }): Json<IdentityDiffBody>,
+) -> impl IntoResponse {
+    let diff = ctx.identity_revision_diff(rid, base, head)?;
+
+    Ok::<_, Error>(Json(diff))
+}
+
+// Deserialize only: `Proposal` is an inbound type and isn't Serialize.
+#[derive(Deserialize)]
+struct ProposeIdentityRevisionBody {
+    pub rid: identity::RepoId,
+    pub proposal: types::cobs::identity::Proposal,
+    pub opts: types::cobs::CobOptions,
+}
+
+async fn propose_identity_revision_handler(
+    State(ctx): State<Context>,
+    Json(ProposeIdentityRevisionBody {
+        rid,
+        proposal,
+        opts,
+    }): Json<ProposeIdentityRevisionBody>,
+) -> impl IntoResponse {
+    let revision = ctx.propose_identity_revision(rid, proposal, opts)?;
+
+    Ok::<_, Error>(Json(revision))
+}
+
+#[derive(Serialize, Deserialize)]
+struct EditIdentityBody {
+    pub rid: identity::RepoId,
+    pub action: types::cobs::identity::Action,
+    pub opts: types::cobs::CobOptions,
+}
+
+async fn edit_identity_handler(
+    State(ctx): State<Context>,
+    Json(EditIdentityBody { rid, action, opts }): Json<EditIdentityBody>,
+) -> impl IntoResponse {
+    let revision = ctx.edit_identity(rid, action, opts)?;
+
+    Ok::<_, Error>(Json(revision))
+}
@@ -6,9 +6,14 @@ use tokio::net::TcpListener;
 
 use radicle::Profile;
 use radicle::cob::cache::COBS_DB_FILE;
+use radicle::node::NOTIFICATIONS_DB_FILE;
 
+use radicle_types::domain::inbox::service::Service as InboxService;
 use radicle_types::domain::issue::service::Service as IssueService;
 use radicle_types::domain::patch::service::Service as PatchService;
+use radicle_types::domain::search::service::Service as SearchService;
+use radicle_types::outbound::inbox::InboxStore;
+use radicle_types::outbound::search::SearchIndex;
 
 mod api;
 
@@ -28,14 +33,24 @@ pub async fn run(options: Options) -> anyhow::Result<()> {
 }
 
 fn router(profile: Profile) -> anyhow::Result<Router> {
-    let profile = Arc::new(profile);
-
+    let inbox_db = radicle_types::outbound::sqlite::Sqlite::reader(
+        profile.node().join(NOTIFICATIONS_DB_FILE),
+    )?;
     let cobs_db =
         radicle_types::outbound::sqlite::Sqlite::reader(profile.cobs().join(COBS_DB_FILE))?;
     let patch_service = PatchService::new(cobs_db.clone());
-    let issue_service = IssueService::new(cobs_db);
-
-    let ctx = api::Context::new(profile, Arc::new(patch_service), Arc::new(issue_service));
+    let issue_service = IssueService::new(cobs_db.clone());
+    // Nothing to keep between runs, so the inbox states and index live in memory.
+    let inbox_service = InboxService::new(InboxStore::open(":memory:", inbox_db)?);
+    let search_service = SearchService::new(SearchIndex::open(":memory:", cobs_db)?);
+
+    let ctx = api::Context::new(
+        profile,
+        Arc::new(patch_service),
+        Arc::new(issue_service),
+        Arc::new(inbox_service),
+        Arc::new(search_service),
+    );
 
     Ok(api::router(ctx))
 }
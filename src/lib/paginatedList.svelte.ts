@@ -10,10 +10,13 @@ export interface PaginatedListOptions<T> {
   key: () => string;
   // The route loader's freshly fetched first page (reactive prop access).
   page: () => PaginatedQuery<T[]>;
-  // Fetch a page from the backend; `take === undefined` means all rows.
+  // Fetch a page from the backend; `take === undefined` means all rows. With
+  // `after` set the page starts right after that cursor and `skip` is
+  // ignored.
   fetchPage: (
     skip: number,
     take: number | undefined,
+    after?: string,
   ) => Promise<PaginatedQuery<T[]>>;
   pageSize: number;
   // Stable identity used to dedupe overlapping pages.
@@ -43,6 +46,7 @@ export function createPaginatedList<T>(opts: PaginatedListOptions<T>) {
   // revalidate call below).
   let seedItems: T[];
   let seedMore: boolean;
+  let seedNext: string | undefined;
   let staleWindow: number | undefined;
   if (restored !== undefined && restored.items.length > opts.pageSize) {
     seedItems = restored.items;
@@ -51,6 +55,7 @@ export function createPaginatedList<T>(opts: PaginatedListOptions<T>) {
   } else {
     seedItems = opts.page().content;
     seedMore = opts.page().more;
+    seedNext = opts.page().next;
   }
 
   let items = $state(seedItems);
@@ -58,6 +63,11 @@ export function createPaginatedList<T>(opts: PaginatedListOptions<T>) {
   // from items.length because appends are deduped (overlapping pages, e.g.
   // when the list grows underneath us, must still advance the offset).
   let cursor = seedItems.length;
+  // Keyset cursor of the last row fetched. Preferred over the offset: it
+  // stays put when rows are added or the cache is rebuilt between page
+  // loads. Unknown for a restored snapshot until it is revalidated, in which
+  // case the offset is used.
+  let next = seedNext;
   let more = $state(seedMore);
   let loadingMore = $state(false);
   let activeKey = opts.key();
@@ -76,6 +86,7 @@ export function createPaginatedList<T>(opts: PaginatedListOptions<T>) {
     activeKey = key;
     items = fresh.content;
     cursor = fresh.content.length;
+    next = fresh.next;
     more = fresh.more;
     // Abort any in-flight loadMore so it cannot append a page from the
     // previous filter onto the just-reset items. The aborted call leaves
@@ -92,7 +103,9 @@ export function createPaginatedList<T>(opts: PaginatedListOptions<T>) {
     let superseded = false;
     try {
       const page = await loader.run(async () =>
-        opts.fetchPage(all ? 0 : cursor, all ? undefined : opts.pageSize),
+        all
+          ? opts.fetchPage(0, undefined)
+          : opts.fetchPage(cursor, opts.pageSize, next),
       );
 
       // Superseded by a newer load (e.g. fuzzy-focus triggered a load-all).
@@ -105,6 +118,7 @@ export function createPaginatedList<T>(opts: PaginatedListOptions<T>) {
       }
 
       more = page.more;
+      next = page.next;
       if (all) {
         items = page.content;
         cursor = page.content.length;
@@ -148,6 +162,7 @@ export function createPaginatedList<T>(opts: PaginatedListOptions<T>) {
       }
       items = page.content;
       cursor = page.content.length;
+      next = page.next;
       more = page.more;
     } catch (error) {
       // The history-restore call site is fire-and-forget; a failed refresh
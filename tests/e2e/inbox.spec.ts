@@ -0,0 +1,50 @@
+import { expect, test } from "@tests/support/fixtures.js";
+
+// Clearing changes what the other tests see, so they run one after another
+// and the cleared notifications are marked unread again afterwards.
+test.describe.configure({ mode: "serial" });
+
+test.afterEach(async ({ request }) => {
+  await request.post(
+    `http://127.0.0.1:${process.env.VITE_TEST_HTTP_API_PORT}/mark_notifications`,
+    { data: { params: { type: "all" }, state: "unread" } },
+  );
+});
+
+test("show notifications grouped by repo", async ({ page }) => {
+  await page.goto("/inbox");
+
+  const header = (name: string) =>
+    page.locator(".header").filter({ hasText: name });
+  await expect(header("Inbox").locator(".global-counter-badge")).toHaveText(
+    "3",
+  );
+  await expect(header("cobs").locator(".global-counter-badge")).toHaveText(
+    "2",
+  );
+  await expect(header("markdown").locator(".global-counter-badge")).toHaveText(
+    "1",
+  );
+  await expect(page.getByText("Rewrite subtitle to README")).toBeVisible();
+  await expect(page.getByText("Add subtitle to README")).toBeVisible();
+});
+
+test("clear notifications", async ({ page }) => {
+  await page.goto("/inbox");
+
+  const markdown = page.locator(".header").filter({ hasText: "markdown" });
+  await markdown.hover();
+  await markdown.locator(".clear-repo").getByRole("button").click();
+  await markdown.getByRole("button", { name: "Mark 1 done" }).click();
+  await expect(markdown).toBeHidden();
+  await expect(
+    page
+      .locator(".header")
+      .filter({ hasText: "Inbox" })
+      .locator(".global-counter-badge"),
+  ).toHaveText("2");
+
+  await page.locator(".clear-inbox").getByRole("button").click();
+  await page.getByRole("button", { name: "Mark 2 done" }).click();
+  await expect(page.getByText("You're all caught up")).toBeVisible();
+});
@@ -0,0 +1,20 @@
+-- Notifications palm's node would have stored after the cobs and markdown
+-- fixtures, loaded in place of what the node recorded so the inbox tests
+-- don't depend on fetch timing. `{{name}}` placeholders are filled in with the
+-- ids of the objects the fixtures created.
+CREATE TABLE IF NOT EXISTS "repository-notifications" (
+  "id"        integer primary key autoincrement,
+  "repo"      text not null,
+  "ref"       text not null,
+  "old"       text,
+  "new"       text,
+  "timestamp" integer not null,
+  unique (repo, ref)
+) strict;
+
+DELETE FROM "repository-notifications";
+
+INSERT INTO "repository-notifications" (repo, ref, old, new, timestamp) VALUES
+  ('{{cobRid}}', 'refs/namespaces/{{remote}}/refs/cobs/xyz.radicle.patch/{{patchThree}}', NULL, '{{patchThree}}', 1671125288000),
+  ('{{cobRid}}', 'refs/namespaces/{{remote}}/refs/cobs/xyz.radicle.patch/{{patchTwo}}', NULL, '{{patchTwo}}', 1671125287000),
+  ('{{markdownRid}}', 'refs/namespaces/{{remote}}/refs/cobs/xyz.radicle.issue/{{markdownIssue}}', NULL, '{{markdownIssue}}', 1671125286000);
@@ -3,6 +3,7 @@ import type { PeerManager, RadiclePeer } from "./peerManager.js";
 
 import * as Fs from "node:fs/promises";
 import * as Path from "node:path";
+import { DatabaseSync } from "node:sqlite";
 import type * as Stream from "node:stream";
 
 import { expect, test as base } from "@playwright/test";
@@ -453,6 +454,8 @@ export async function createCobsFixture(
     ["patch", "ready", patchFive, "--undo"],
     createOptions(repoFolder, 1),
   );
+
+  return { remote: eve.nodeId, patchTwo, patchThree };
 }
 
 export async function createMarkdownFixture(peer: RadiclePeer) {
@@ -479,13 +482,48 @@ export async function createMarkdownFixture(peer: RadiclePeer) {
     cwd: repoFolder,
   });
   await peer.git(["push", "rad"], { cwd: repoFolder });
-  await issue.create(
+  const markdownIssue = await issue.create(
     peer,
     "This `title` has **markdown**",
     'This is a description\n\nWith some multiline text.\n\n```\n23-11-06 10:19 ➜  radicle-jetbrains-plugin git:(main) rad id update --title "Godify jchrist" --description "where jchrist ascends to a god of this project" --delegate did:key:z6MkpaATbhkGbSMysNomYTFVvKG5bnNKYZ2cCamfoHzX9SnL --threshold 1\n\n✓ Identity revision 029837dde8f5c49704e50a19cd709473ac66a456 created\n```',
     ["bug", "feature-request"],
     { cwd: repoFolder },
   );
+
+  return { markdownIssue };
+}
+
+// Replaces what the node recorded with the notifications in
+// `fixtures/inbox/notifications.sql`. The node must not be running.
+export async function createInboxFixture(
+  peer: RadiclePeer,
+  ids: {
+    remote: string;
+    patchTwo: string;
+    patchThree: string;
+    markdownIssue: string;
+  },
+) {
+  const template = await Fs.readFile(
+    Path.join(fixturesDir, "inbox", "notifications.sql"),
+    "utf-8",
+  );
+  const values: Record<string, string> = { cobRid, markdownRid, ...ids };
+  const sql = template.replace(/\{\{(\w+)\}\}/g, (_, name: string) => {
+    if (!(name in values)) {
+      throw new Error(`No value for {{${name}}} in the inbox fixture`);
+    }
+    return values[name];
+  });
+
+  const db = new DatabaseSync(
+    Path.join(peer.radHome, "node", "notifications.db"),
+  );
+  try {
+    db.exec(sql);
+  } finally {
+    db.close();
+  }
 }
 
 export const aliceMainHead = "7babd25a74eb3752ec24672b5edf0e7ecb4daf24";
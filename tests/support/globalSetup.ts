@@ -3,6 +3,7 @@ import * as Path from "node:path";
 
 import {
   createCobsFixture,
+  createInboxFixture,
   createMarkdownFixture,
   defaultConfig,
   gitOptions,
@@ -71,9 +72,12 @@ export default async function globalSetup(): Promise<() => void> {
 
     try {
       console.log("Creating markdown fixture");
-      await createMarkdownFixture(palm);
+      const markdown = await createMarkdownFixture(palm);
       console.log("Creating cobs fixture");
-      await createCobsFixture(peerManager, palm);
+      const cobs = await createCobsFixture(peerManager, palm);
+      await palm.stopNode();
+      console.log("Creating inbox fixture");
+      await createInboxFixture(palm, { ...markdown, ...cobs });
       console.log("All fixtures created");
     } catch (error) {
       console.log("");
@@ -84,7 +88,6 @@ export default async function globalSetup(): Promise<() => void> {
       console.log("");
       process.exit(1);
     }
-    await palm.stopNode();
   } else {
     await palm.startHttpd(parseInt(process.env.VITE_TEST_HTTP_API_PORT ?? "0"));
   }
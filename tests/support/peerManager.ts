@@ -371,6 +371,10 @@ export class RadiclePeer {
     return `/nodes/${this.httpdBaseUrl.hostname}:${this.httpdBaseUrl.port}/${rid}`;
   }
 
+  public get radHome(): string {
+    return this.#radHome;
+  }
+
   public get httpdBaseUrl(): BaseUrl {
     if (!this.#httpdBaseUrl) {
       throw new Error("No httpd service running");